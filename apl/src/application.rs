@@ -9,7 +9,7 @@ pub use winit::{
     window::Icon,
 };
 
-pub use sgl::{HasContext};
+pub use sgl::{HasContext, DeletionQueue};
 
 /// Keyboard key name
 /*
@@ -95,6 +95,12 @@ pub trait EventHandler {
     /// Handle file drop
     fn file_drop(&mut self, _path: PathBuf) {}
 
+    /// Handle deletion queue of context
+    ///
+    /// Called when context is created. Objects attached to the queue is deleted before redraw.
+    /// The queue is current while context exists, so objects is attached to it on creation.
+    fn queue(&mut self, _queue: &DeletionQueue<Self::Context>) {}
+
     /// Handle sizing
     fn reconf(&mut self, _conf: ViewConfig, _gl: &Self::Context) {}
 
@@ -111,6 +117,15 @@ pub trait EventHandler {
     fn resume(&mut self) {}
 }
 
+/// Decode window icon from PNG or JPEG image
+#[cfg(feature = "image")]
+pub fn icon_from_image_bytes(data: &[u8]) -> sgl::Result<Icon> {
+    let image = sgl::decode_image::<sgl::RGBA8888>(data, sgl::DecodeOptions::new())?;
+    let (width, height) = image.size();
+    Icon::from_rgba(image.as_bytes().into(), width as u32, height as u32)
+        .map_err(|error| sgl::Error::Decode(error.to_string()))
+}
+
 /// Application config
 pub struct AppConfig {
     /// Window title
@@ -19,7 +19,7 @@ use glutin::{
     dpi::LogicalSize,
 };
 
-use sgl::{GL, HasContext, Context as GlContext};
+use sgl::{GL, HasContext, Context as GlContext, DeletionQueue};
 
 use super::{Key, EventHandler, ViewConfig, AppConfig};
 
@@ -31,6 +31,7 @@ struct View {
     pixel_format: PixelFormat,
     gl_size: (i32, i32),
     gl: GlContext,
+    queue: DeletionQueue<GlContext>,
 }
 
 impl View {
@@ -41,6 +42,11 @@ impl View {
             |proc_name| gl_context.get_proc_address(proc_name)
         );
 
+        sgl::make_current(&gl, |proc_name| gl_context.get_proc_address(proc_name));
+
+        let queue = DeletionQueue::new();
+        queue.make_current();
+
         let gl_api = match gl_context.get_api() {
             Api::OpenGl => "OpenGL",
             Api::OpenGlEs => "OpenGLES",
@@ -92,10 +98,13 @@ impl View {
             pixel_format,
             gl_size,
             gl,
+            queue,
         })
     }
 
     fn teardown(mut self) -> ContextWrapper<NotCurrent, Window> {
+        self.queue.flush(&self.gl);
+        sgl::release_current();
         unsafe { self.gl_context.take().unwrap().treat_as_not_current() }
     }
 
@@ -140,6 +149,7 @@ impl View {
     where
         H: EventHandler<Context = GlContext>
     {
+        self.queue.flush(&self.gl);
         handler.redraw(&self.gl);
         self.gl_context.as_ref().unwrap().swap_buffers().unwrap();
     }
@@ -260,6 +270,7 @@ impl Platform {
         #[cfg(not(target_os = "android"))]
         match View::try_init(gl_context.take().unwrap()) {
             Ok(v) => {
+                handler.queue(&v.queue);
                 view = v.into();
             },
             Err((c, e)) => {
@@ -299,6 +310,7 @@ impl Platform {
                     if view.is_none() {
                         match View::try_init(gl_context.take().unwrap()) {
                             Ok(v) => {
+                                handler.queue(&v.queue);
                                 view = v.into();
                                 handler.resume();
                             },
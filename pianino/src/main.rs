@@ -3,7 +3,7 @@ use std::{
     path::PathBuf
 };
 
-use apl::{Key, Button, Phase, EventHandler, Platform, ViewConfig, AppConfig, Icon};
+use apl::{Key, Button, Phase, EventHandler, Platform, ViewConfig, AppConfig, icon_from_image_bytes};
 use sgl::{GL, HasContext, Context, demo::Demo};
 
 pub struct Application<G: HasContext> {
@@ -109,11 +109,7 @@ fn main() {
 
     let config = AppConfig {
         title: "Pianino".into(),
-        icon: Icon::from_rgba(
-            include_bytes!("icon.rgba")
-                .as_ref().into(),
-            64, 64
-        ).ok(),
+        icon: icon_from_image_bytes(include_bytes!("icon.png")).ok(),
     };
 
     let platform = Platform::new(config);
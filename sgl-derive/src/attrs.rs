@@ -0,0 +1,62 @@
+use syn::{Attribute, Meta, NestedMeta, Lit, Error, Result, Data, DataStruct, Fields, FieldsNamed, DeriveInput};
+
+/// Options of struct field
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Use different name
+    pub rename: Option<String>,
+    /// Ignore field
+    pub skip: bool,
+}
+
+impl FieldAttrs {
+    /// Parse field options from attributes with specified name
+    pub fn parse(attrs: &[Attribute], name: &str) -> Result<Self> {
+        let mut result = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, format!("Expected #[{}(...)]", name))),
+            };
+            for item in list.nested {
+                match item {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => {
+                        result.skip = true;
+                    },
+                    NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident("rename") => {
+                        match pair.lit {
+                            Lit::Str(value) => result.rename = Some(value.value()),
+                            lit => return Err(Error::new_spanned(lit, "Expected string literal")),
+                        }
+                    },
+                    item => return Err(Error::new_spanned(item, "Unknown option")),
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Check that struct has `#[repr(C)]`
+pub fn has_repr_c(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list.nested.iter().any(|item| match item {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("C"),
+                _ => false,
+            }),
+            _ => false,
+        })
+}
+
+/// Get named fields of struct
+pub fn named_fields(input: &DeriveInput) -> Result<&FieldsNamed> {
+    match &input.data {
+        Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => Ok(fields),
+        _ => Err(Error::new_spanned(input, "Only structs with named fields is supported")),
+    }
+}
@@ -0,0 +1,49 @@
+extern crate proc_macro;
+
+mod attrs;
+mod vertex;
+mod uniforms;
+mod pod;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive `sgl::Vertex` for `#[repr(C)]` struct
+///
+/// Each field is bound to attribute with same name.
+/// Use `#[vertex(rename = "a_name")]` to bind field to attribute with different name
+/// and `#[vertex(skip)]` to ignore field (like padding).
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex::derive(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derive `sgl::Uniforms` for struct of uniform values
+///
+/// Generates `<Name>Bindings` struct which resolves locations of all uniforms
+/// using `new(gl, &program)` and loads all values using `load(gl, &values)`.
+/// Each field is bound to uniform with same name.
+/// Use `#[uniform(rename = "u_name")]` to bind field to uniform with different name
+/// and `#[uniform(skip)]` to ignore field.
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniforms::derive(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Derive `sgl::Pod` for `#[repr(C)]` struct
+///
+/// Checks that all fields is `Pod` and struct has no padding,
+/// so it can be used as buffer element or texture pixel.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pod::derive(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
@@ -0,0 +1,32 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, Result};
+use super::attrs::{has_repr_c, named_fields};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    if !has_repr_c(&input.attrs) {
+        return Err(Error::new_spanned(&input.ident, "Pod struct must be #[repr(C)]"));
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "Pod struct must not be generic"));
+    }
+
+    let types = named_fields(&input)?.named.iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+
+    let ident = &input.ident;
+
+    Ok(quote! {
+        unsafe impl ::sgl::Pod for #ident {}
+
+        const _: fn() = || {
+            fn assert_pod<T: ::sgl::Pod>() {}
+            #(assert_pod::<#types>();)*
+        };
+
+        const _: () = assert!(::core::mem::size_of::<#ident>() == 0 #(+ ::core::mem::size_of::<#types>())*,
+                              "Pod struct must not have padding");
+    })
+}
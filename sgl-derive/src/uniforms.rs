@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream;
+use quote::{quote, format_ident};
+use syn::{DeriveInput, Error, Result};
+use super::attrs::{FieldAttrs, named_fields};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "Generic uniforms structs is not supported"));
+    }
+
+    let mut fields = Vec::new();
+    let mut lookups = Vec::new();
+    let mut loads = Vec::new();
+    let mut relocates = Vec::new();
+
+    for field in &named_fields(&input)?.named {
+        let attrs = FieldAttrs::parse(&field.attrs, "uniform")?;
+        if attrs.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let name = attrs.rename.unwrap_or_else(|| ident.to_string());
+        let typ = &field.ty;
+
+        fields.push(quote! { #ident: ::sgl::Uniform<G, #typ> });
+        lookups.push(quote! { #ident: program.uniform(gl, #name)? });
+        loads.push(quote! { self.#ident.load(gl, ::core::clone::Clone::clone(&values.#ident)); });
+        relocates.push(quote! { ::sgl::Relocate::relocate(&mut self.#ident, gl, program)?; });
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let bindings = format_ident!("{}Bindings", ident);
+    let doc = format!("Uniform locations binding for [`{}`]", ident);
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #bindings<G: ::sgl::HasContext> {
+            #(#fields,)*
+        }
+
+        impl<G: ::sgl::HasContext> #bindings<G> {
+            /// Resolve uniform locations
+            #vis fn new(gl: &G, program: &::sgl::Program<G>) -> ::sgl::Result<Self> {
+                Ok(Self { #(#lookups,)* })
+            }
+
+            /// Load all values to uniforms
+            #vis fn load(&self, gl: &G, values: &#ident) {
+                #(#loads)*
+            }
+        }
+
+        impl<G: ::sgl::HasContext> ::sgl::Relocate<G> for #bindings<G> {
+            fn relocate(&mut self, gl: &G, program: &::sgl::Program<G>) -> ::sgl::Result<()> {
+                #(#relocates)*
+                Ok(())
+            }
+        }
+
+        impl<G: ::sgl::HasContext> ::sgl::Uniforms<G> for #ident {
+            type Bindings = #bindings<G>;
+
+            fn bindings(gl: &G, program: &::sgl::Program<G>) -> ::sgl::Result<Self::Bindings> {
+                #bindings::new(gl, program)
+            }
+
+            fn load(bindings: &Self::Bindings, gl: &G, values: &Self) {
+                bindings.load(gl, values)
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, Result, parse_quote};
+use super::attrs::{FieldAttrs, has_repr_c, named_fields};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    if !has_repr_c(&input.attrs) {
+        return Err(Error::new_spanned(&input.ident, "Vertex struct must be #[repr(C)]"));
+    }
+
+    let mut fields = Vec::new();
+
+    for field in &named_fields(&input)?.named {
+        let attrs = FieldAttrs::parse(&field.attrs, "vertex")?;
+        if attrs.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let name = attrs.rename.unwrap_or_else(|| ident.to_string());
+        let typ = &field.ty;
+
+        fields.push(quote! {
+            ::sgl::VertexField::new::<#typ>(#name, ::core::mem::offset_of!(Self, #ident))
+        });
+    }
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(G: ::sgl::HasContext));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::sgl::Vertex<G> for #ident #ty_generics #where_clause {
+            fn fields() -> Vec<::sgl::VertexField<G>> {
+                vec![#(#fields),*]
+            }
+        }
+    })
+}
@@ -1,8 +1,8 @@
 use core::{
-    mem::size_of,
+    mem::{size_of, offset_of},
     marker::PhantomData
 };
-use super::{Result, GL, HasContext, Buffer, Array};
+use super::{Result, GL, HasContext, GlslType, Buffer, Array, Pod, Vector2, Vector3, Vector4, context::Current};
 
 #[cfg(feature = "glam")]
 mod glam_impls;
@@ -15,13 +15,19 @@ mod colours_impls;
 
 /// Attribute location binding
 pub struct Attrib<G: HasContext, T: AsAttrib<G>> {
+    pub(super) name: String,
     pub(super) attrib: Option<u32>,
     _gl: PhantomData<(G, T)>,
 }
 
 impl<G: HasContext, T: AsAttrib<G>> Attrib<G, T> {
-    pub(super) fn new(attrib: Option<u32>) -> Self {
-        Self { attrib, _gl: PhantomData }
+    pub(super) fn new(name: &str, attrib: Option<u32>) -> Self {
+        Self { name: name.into(), attrib, _gl: PhantomData }
+    }
+
+    /// Attribute name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Enable attribute
@@ -38,6 +44,13 @@ impl<G: HasContext, T: AsAttrib<G>> Attrib<G, T> {
         }
     }
 
+    /// Set attribute divisor
+    fn divisor(&self, gl: &G, divisor: u32) {
+        if let Some(attrib) = &self.attrib {
+            unsafe { Current::get(gl).vertex_attrib_divisor(gl, *attrib, divisor); }
+        }
+    }
+
     /// Setup attribute pointer
     pub fn pointer(&self, gl: &G, offset: i32, stride: i32) {
         if let Some(attrib) = &self.attrib {
@@ -46,6 +59,11 @@ impl<G: HasContext, T: AsAttrib<G>> Attrib<G, T> {
     }
 }
 
+/// The trait for attributes which can be bound to vertex buffer
+///
+/// Single [`Attrib`] binds tightly packed values.
+/// Interleaved attributes is bound using [`VertexAttribs`](super::VertexAttribs) of `#[derive(Vertex, Pod)]` struct
+/// or using tuple of [`Attrib`]s, which takes offsets of fields from layout of tuple type.
 pub trait Attribs<G: HasContext> {
     /// Attributes type
     type Type;
@@ -56,52 +74,86 @@ pub trait Attribs<G: HasContext> {
     /// Disable attributes
     fn disable_attribs(&self, gl: &G);
 
+    /// Set divisor of attributes
+    ///
+    /// Zero means that attributes advances per vertex,
+    /// otherwise attributes advances once per specified number of instances.
+    fn set_divisor(&self, gl: &G, divisor: u32);
+
+    /// Size of vertex data in bytes
+    fn stride(&self) -> i32 {
+        size_of::<Self::Type>() as i32
+    }
+
     /// Create suitable buffer
-    fn buffer(&self, gl: &G) -> Result<Buffer<G, Array<Self::Type>>> {
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    fn buffer(&self, gl: &G) -> Result<Buffer<G, Array<Self::Type>>> where G: 'static, Self::Type: Pod {
         Buffer::new(gl)
     }
 }
 
-macro_rules! bytelen {
-    ($($type:ty),*) => {
-        ( $( size_of::<$type>() +)* 0) as i32
-    };
-}
-
 impl<G: HasContext, T: AsAttrib<G>> Attribs<G> for Attrib<G, T> {
     type Type = T;
 
     fn enable_attribs(&self, gl: &G) {
         self.enable_attrib(gl);
-        self.pointer(gl, 0, bytelen!(T));
+        self.pointer(gl, 0, size_of::<T>() as i32);
     }
 
     fn disable_attribs(&self, gl: &G) {
         self.disable_attrib(gl);
     }
+
+    fn set_divisor(&self, gl: &G, divisor: u32) {
+        self.divisor(gl, divisor);
+    }
+}
+
+impl<G: HasContext, A: Attribs<G>> Attribs<G> for &A {
+    type Type = A::Type;
+
+    fn enable_attribs(&self, gl: &G) {
+        (*self).enable_attribs(gl);
+    }
+
+    fn disable_attribs(&self, gl: &G) {
+        (*self).disable_attribs(gl);
+    }
+
+    fn set_divisor(&self, gl: &G, divisor: u32) {
+        (*self).set_divisor(gl, divisor);
+    }
+
+    fn stride(&self) -> i32 {
+        (*self).stride()
+    }
 }
 
 macro_rules! attribs_impls {
     ($($($Tx: ident, $x: tt),+;)*) => {
         $(
+            /// Interleaved attributes which is laid out like tuple
+            ///
+            /// Rust doesn't guarantee order of tuple fields in memory,
+            /// so vertex data should be stored as values of the same tuple type.
             impl<G: HasContext, $($Tx: AsAttrib<G>),+> Attribs<G> for ($(Attrib<G, $Tx>),+) {
                 type Type = ($($Tx),+);
 
                 fn enable_attribs(&self, gl: &G) {
                     $(self.$x.enable_attrib(gl);)+
 
-                    let stride = bytelen!($($Tx),+);
+                    let stride = self.stride();
 
-                    let _offset = 0;
-                    $(
-                        self.$x.pointer(gl, _offset, stride);
-                        let _offset = _offset + bytelen!($Tx);
-                    )+
+                    $(self.$x.pointer(gl, offset_of!(Self::Type, $x) as i32, stride);)+
                 }
 
                 fn disable_attribs(&self, gl: &G) {
                     $(self.$x.disable_attrib(gl);)+
                 }
+
+                fn set_divisor(&self, gl: &G, divisor: u32) {
+                    $(self.$x.divisor(gl, divisor);)+
+                }
             }
         )*
     };
@@ -120,13 +172,17 @@ attribs_impls! {
 
 /// The trait for types which can be used as a vertex attribute
 pub trait AsAttrib<G> {
+    /// GLSL type of attribute
+    const TYPE: GlslType;
+
     fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32);
 }
 
 macro_rules! as_attrib_impls_f32 {
-    ($($type: ty, $gl_type: ident, $size: tt, $norm: ident;)*) => {
+    ($($type: ty, $glsl_type: ident, $gl_type: ident, $size: tt, $norm: ident;)*) => {
         $(
             impl<G: HasContext> AsAttrib<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32) {
                     unsafe { gl.vertex_attrib_pointer_f32(attrib, $size, GL::$gl_type, $norm, stride, offset); }
                 }
@@ -136,9 +192,10 @@ macro_rules! as_attrib_impls_f32 {
 }
 
 macro_rules! as_attrib_impls_i32 {
-    ($($type: ty, $gl_type: ident, $size: tt;)*) => {
+    ($($type: ty, $glsl_type: ident, $gl_type: ident, $size: tt;)*) => {
         $(
             impl<G: HasContext> AsAttrib<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32) {
                     unsafe { gl.vertex_attrib_pointer_i32(attrib, $size, GL::$gl_type, stride, offset); }
                 }
@@ -148,27 +205,225 @@ macro_rules! as_attrib_impls_i32 {
 }
 
 as_attrib_impls_f32! {
-    f32, FLOAT, 1, false;
-    (f32, f32), FLOAT, 2, false;
-    (f32, f32, f32), FLOAT, 3, false;
-    (f32, f32, f32, f32), FLOAT, 4, false;
+    f32, Float, FLOAT, 1, false;
+    Vector2<f32>, Vec2, FLOAT, 2, false;
+    Vector3<f32>, Vec3, FLOAT, 3, false;
+    Vector4<f32>, Vec4, FLOAT, 4, false;
+}
+
+/// Integer attribute which is converted to float with normalization
+///
+/// Unsigned values is mapped to `[0, 1]` and signed values to `[-1, 1]` range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Normalized<T>(pub T);
+
+unsafe impl<T: Pod> Pod for Normalized<T> {}
+
+/// Integer attribute which is passed to shader as is
+///
+/// NOTE: Requires OpenGL ES 3.0+, OpenGL 3.0+ or WebGL 2.0.
+/// Plain integer types is converted to float without normalization.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Integer<T>(pub T);
+
+unsafe impl<T: Pod> Pod for Integer<T> {}
+
+/// Half precision float
+///
+/// NOTE: Attributes of this type requires OpenGL ES 3.0+, OpenGL 3.0+ or WebGL 2.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Half(pub u16);
+
+unsafe impl Pod for Half {}
+
+impl Half {
+    /// Convert from single precision float with rounding
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exp = ((bits >> 23) & 0xff) as i32;
+        let man = bits & 0x7f_ffff;
+
+        if exp == 0xff {
+            // infinity or NaN
+            return Half(sign | 0x7c00 | if man != 0 { 0x200 } else { 0 });
+        }
+
+        let exp = exp - 127 + 15;
+
+        if exp >= 0x1f {
+            // too big
+            return Half(sign | 0x7c00);
+        }
+
+        if exp <= 0 {
+            if exp < -10 {
+                // too small
+                return Half(sign);
+            }
+            // subnormal
+            let man = man | 0x80_0000;
+            let shift = (14 - exp) as u32;
+            let round = (man >> (shift - 1)) & 1;
+            return Half(sign | ((man >> shift) + round) as u16);
+        }
+
+        let round = (man >> 12) & 1;
+        Half(sign | ((((exp as u32) << 10) | (man >> 13)) + round) as u16)
+    }
+
+    /// Convert to single precision float
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1f) as u32;
+        let man = (self.0 & 0x3ff) as u32;
+
+        f32::from_bits(match exp {
+            0 if man == 0 => sign,
+            0 => {
+                // subnormal
+                let value = man as f32 / (1 << 24) as f32;
+                return if sign != 0 { -value } else { value };
+            },
+            0x1f => sign | 0x7f80_0000 | (man << 13),
+            _ => sign | ((exp + 127 - 15) << 23) | (man << 13),
+        })
+    }
+}
+
+impl From<f32> for Half {
+    fn from(value: f32) -> Self {
+        Self::from_f32(value)
+    }
+}
+
+impl From<Half> for f32 {
+    fn from(value: Half) -> Self {
+        value.to_f32()
+    }
+}
+
+/// Packed signed 4-component vector with 10 bits for x, y, z and 2 bits for w
+///
+/// Corresponds to `INT_2_10_10_10_REV` type.
+/// Use with [`Normalized`] to get components in `[-1, 1]` range.
+///
+/// NOTE: Requires OpenGL ES 3.0+, OpenGL 3.3+ or WebGL 2.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Int2101010Rev(pub u32);
+
+unsafe impl Pod for Int2101010Rev {}
+
+impl Int2101010Rev {
+    /// Pack components
+    ///
+    /// The x, y, z values should be in `[-512, 511]` and w value in `[-2, 1]` range.
+    pub fn new(x: i16, y: i16, z: i16, w: i8) -> Self {
+        Self((x as u32 & 0x3ff) |
+             ((y as u32 & 0x3ff) << 10) |
+             ((z as u32 & 0x3ff) << 20) |
+             ((w as u32 & 0x3) << 30))
+    }
+
+    /// Pack components in `[-1, 1]` range for normalized attribute
+    pub fn from_unit(x: f32, y: f32, z: f32, w: f32) -> Self {
+        let unit = |value: f32, max: f32| (value.clamp(-1.0, 1.0) * max).round();
+        Self::new(unit(x, 511.0) as i16,
+                  unit(y, 511.0) as i16,
+                  unit(z, 511.0) as i16,
+                  unit(w, 1.0) as i8)
+    }
+}
+
+/// Packed unsigned 4-component vector with 10 bits for x, y, z and 2 bits for w
+///
+/// Corresponds to `UNSIGNED_INT_2_10_10_10_REV` type.
+/// Use with [`Normalized`] to get components in `[0, 1]` range.
+///
+/// NOTE: Requires OpenGL ES 3.0+, OpenGL 3.3+ or WebGL 2.0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct UInt2101010Rev(pub u32);
+
+unsafe impl Pod for UInt2101010Rev {}
+
+impl UInt2101010Rev {
+    /// Pack components
+    ///
+    /// The x, y, z values should be in `[0, 1023]` and w value in `[0, 3]` range.
+    pub fn new(x: u16, y: u16, z: u16, w: u8) -> Self {
+        Self((x as u32 & 0x3ff) |
+             ((y as u32 & 0x3ff) << 10) |
+             ((z as u32 & 0x3ff) << 20) |
+             ((w as u32 & 0x3) << 30))
+    }
+
+    /// Pack components in `[0, 1]` range for normalized attribute
+    pub fn from_unit(x: f32, y: f32, z: f32, w: f32) -> Self {
+        let unit = |value: f32, max: f32| (value.clamp(0.0, 1.0) * max).round();
+        Self::new(unit(x, 1023.0) as u16,
+                  unit(y, 1023.0) as u16,
+                  unit(z, 1023.0) as u16,
+                  unit(w, 3.0) as u8)
+    }
+}
+
+as_attrib_impls_f32! {
+    Half, Float, HALF_FLOAT, 1, false;
+    Vector2<Half>, Vec2, HALF_FLOAT, 2, false;
+    Vector3<Half>, Vec3, HALF_FLOAT, 3, false;
+    Vector4<Half>, Vec4, HALF_FLOAT, 4, false;
+    Int2101010Rev, Vec4, INT_2_10_10_10_REV, 4, false;
+    Normalized<Int2101010Rev>, Vec4, INT_2_10_10_10_REV, 4, true;
+    UInt2101010Rev, Vec4, UNSIGNED_INT_2_10_10_10_REV, 4, false;
+    Normalized<UInt2101010Rev>, Vec4, UNSIGNED_INT_2_10_10_10_REV, 4, true;
+}
+
+macro_rules! as_attrib_impls_int {
+    ($($type: ty, $glsl_type: ident, $int_type: ident, $gl_type: ident, $size: tt;)*) => {
+        as_attrib_impls_f32! {
+            $(
+                $type, $glsl_type, $gl_type, $size, false;
+                Normalized<$type>, $glsl_type, $gl_type, $size, true;
+            )*
+        }
+
+        as_attrib_impls_i32! {
+            $(Integer<$type>, $int_type, $gl_type, $size;)*
+        }
+    };
+}
+
+as_attrib_impls_int! {
+    i8, Float, Int, BYTE, 1;
+    Vector2<i8>, Vec2, IVec2, BYTE, 2;
+    Vector3<i8>, Vec3, IVec3, BYTE, 3;
+    Vector4<i8>, Vec4, IVec4, BYTE, 4;
+    u8, Float, UInt, UNSIGNED_BYTE, 1;
+    Vector2<u8>, Vec2, UVec2, UNSIGNED_BYTE, 2;
+    Vector3<u8>, Vec3, UVec3, UNSIGNED_BYTE, 3;
+    Vector4<u8>, Vec4, UVec4, UNSIGNED_BYTE, 4;
+    i16, Float, Int, SHORT, 1;
+    Vector2<i16>, Vec2, IVec2, SHORT, 2;
+    Vector3<i16>, Vec3, IVec3, SHORT, 3;
+    Vector4<i16>, Vec4, IVec4, SHORT, 4;
+    u16, Float, UInt, UNSIGNED_SHORT, 1;
+    Vector2<u16>, Vec2, UVec2, UNSIGNED_SHORT, 2;
+    Vector3<u16>, Vec3, UVec3, UNSIGNED_SHORT, 3;
+    Vector4<u16>, Vec4, UVec4, UNSIGNED_SHORT, 4;
 }
 
 as_attrib_impls_i32! {
-    i8, BYTE, 1;
-    (i8, i8), BYTE, 2;
-    (i8, i8, i8), BYTE, 3;
-    (i8, i8, i8, i8), BYTE, 4;
-    u8, UNSIGNED_BYTE, 1;
-    (u8, u8), UNSIGNED_BYTE, 2;
-    (u8, u8, u8), UNSIGNED_BYTE, 3;
-    (u8, u8, u8, u8), UNSIGNED_BYTE, 4;
-    i16, SHORT, 1;
-    (i16, i16), SHORT, 2;
-    (i16, i16, i16), SHORT, 3;
-    (i16, i16, i16, i16), SHORT, 4;
-    u16, UNSIGNED_SHORT, 1;
-    (u16, u16), UNSIGNED_SHORT, 2;
-    (u16, u16, u16), UNSIGNED_SHORT, 3;
-    (u16, u16, u16, u16), UNSIGNED_SHORT, 4;
+    Integer<i32>, Int, INT, 1;
+    Integer<Vector2<i32>>, IVec2, INT, 2;
+    Integer<Vector3<i32>>, IVec3, INT, 3;
+    Integer<Vector4<i32>>, IVec4, INT, 4;
+    Integer<u32>, UInt, UNSIGNED_INT, 1;
+    Integer<Vector2<u32>>, UVec2, UNSIGNED_INT, 2;
+    Integer<Vector3<u32>>, UVec3, UNSIGNED_INT, 3;
+    Integer<Vector4<u32>>, UVec4, UNSIGNED_INT, 4;
 }
@@ -1,10 +1,11 @@
 use colours::{Rgb, Rgba, Hsv, Hsva, Hsl, Hsla};
-use super::{HasContext, GL, AsAttrib};
+use super::{HasContext, GL, GlslType, AsAttrib};
 
 macro_rules! as_attrib_impls_vec {
-    ($($type: ty, $gl_type: ident, $size: tt, $norm: ident;)*) => {
+    ($($type: ty, $glsl_type: ident, $gl_type: ident, $size: tt, $norm: ident;)*) => {
         $(
             impl<G: HasContext> AsAttrib<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32) {
                     unsafe { gl.vertex_attrib_pointer_f32(attrib, $size, GL::$gl_type, $norm, stride, offset); }
                 }
@@ -14,10 +15,10 @@ macro_rules! as_attrib_impls_vec {
 }
 
 as_attrib_impls_vec! {
-    Rgb<f32>, FLOAT, 3, false;
-    Rgba<f32>, FLOAT, 4, false;
-    Hsv<f32>, FLOAT, 3, false;
-    Hsva<f32>, FLOAT, 4, false;
-    Hsl<f32>, FLOAT, 3, false;
-    Hsla<f32>, FLOAT, 4, false;
+    Rgb<f32>, Vec3, FLOAT, 3, false;
+    Rgba<f32>, Vec4, FLOAT, 4, false;
+    Hsv<f32>, Vec3, FLOAT, 3, false;
+    Hsva<f32>, Vec4, FLOAT, 4, false;
+    Hsl<f32>, Vec3, FLOAT, 3, false;
+    Hsla<f32>, Vec4, FLOAT, 4, false;
 }
@@ -1,10 +1,11 @@
 use glam::{Vec2, Vec3, Vec4};
-use crate::{HasContext, GL, AsAttrib};
+use crate::{HasContext, GL, GlslType, AsAttrib, Pod};
 
 macro_rules! as_attrib_impls_vec {
-    ($($type: ty, $gl_type: ident, $size: tt, $norm: ident;)*) => {
+    ($($type: ty, $glsl_type: ident, $gl_type: ident, $size: tt, $norm: ident;)*) => {
         $(
             impl<G: HasContext> AsAttrib<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32) {
                     unsafe { gl.vertex_attrib_pointer_f32(attrib, $size, GL::$gl_type, $norm, stride, offset); }
                 }
@@ -14,7 +15,10 @@ macro_rules! as_attrib_impls_vec {
 }
 
 as_attrib_impls_vec! {
-    Vec2, FLOAT, 2, false;
-    Vec3, FLOAT, 3, false;
-    Vec4, FLOAT, 4, false;
+    Vec2, Vec2, FLOAT, 2, false;
+    Vec3, Vec3, FLOAT, 3, false;
+    Vec4, Vec4, FLOAT, 4, false;
 }
+
+unsafe impl Pod for Vec2 {}
+unsafe impl Pod for Vec4 {}
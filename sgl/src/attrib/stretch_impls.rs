@@ -1,10 +1,11 @@
 use stretch::geometry::{Point, Size, Rect};
-use super::{HasContext, GL, AsAttrib};
+use super::{HasContext, GL, GlslType, AsAttrib};
 
 macro_rules! as_attrib_impls_vec {
-    ($($type: ty, $gl_type: ident, $size: tt, $norm: ident;)*) => {
+    ($($type: ty, $glsl_type: ident, $gl_type: ident, $size: tt, $norm: ident;)*) => {
         $(
             impl<G: HasContext> AsAttrib<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 fn attrib_pointer(gl: &G, attrib: u32, offset: i32, stride: i32) {
                     unsafe { gl.vertex_attrib_pointer_f32(attrib, $size, GL::$gl_type, $norm, stride, offset); }
                 }
@@ -14,7 +15,7 @@ macro_rules! as_attrib_impls_vec {
 }
 
 as_attrib_impls_vec! {
-    Point<f32>, FLOAT, 2, false;
-    Size<f32>, FLOAT, 2, false;
-    Rect<f32>, FLOAT, 4, false;
+    Point<f32>, Vec2, FLOAT, 2, false;
+    Size<f32>, Vec2, FLOAT, 2, false;
+    Rect<f32>, Vec4, FLOAT, 4, false;
 }
@@ -1,22 +1,31 @@
 use core::{
     marker::PhantomData,
     mem::size_of,
-    slice::from_raw_parts,
-    ops::{RangeBounds, Bound},
+    ptr::copy_nonoverlapping,
+    ops::{Range, RangeBounds, Bound},
 };
-use super::{Result, GL, HasContext, Attribs};
+use super::{Result, Error, Object, GL, HasContext, Api, Attribs, Resource, DeletionQueue, Pod, as_bytes, as_bytes_mut, zeroed_vec, context::Current, track::Track};
+#[cfg(feature = "validate")]
+use super::validate;
 
 pub trait BufferTarget {
     /// OpenGL target
     const TARGET: u32;
 
     /// Data type
-    type Type;
+    type Type: Pod;
+
+    /// Convert data to indexes for validation
+    #[cfg(feature = "validate")]
+    #[doc(hidden)]
+    fn indices(_data: &[Self::Type]) -> Option<Vec<u32>> {
+        None
+    }
 }
 
 pub struct Array<T>(PhantomData<T>);
 
-impl<T> BufferTarget for Array<T> {
+impl<T: Pod> BufferTarget for Array<T> {
     const TARGET: u32 = GL::ARRAY_BUFFER;
     type Type = T;
 }
@@ -26,11 +35,19 @@ pub struct ElementArray<T: AsElement>(PhantomData<T>);
 impl<T: AsElement> BufferTarget for ElementArray<T> {
     const TARGET: u32 = GL::ELEMENT_ARRAY_BUFFER;
     type Type = T;
+
+    #[cfg(feature = "validate")]
+    fn indices(data: &[T]) -> Option<Vec<u32>> {
+        Some(data.iter().map(|index| (*index).into()).collect())
+    }
 }
 
-pub trait AsElement {
+pub trait AsElement: Pod + Into<u32> {
     /// OpenGL data type of element
-    const TYPE: u32 = 0;
+    const TYPE: u32;
+
+    /// Index which restarts primitive (see [`PrimitiveRestart`](super::PrimitiveRestart))
+    const RESTART: Self;
 }
 
 macro_rules! as_element_impl {
@@ -38,6 +55,7 @@ macro_rules! as_element_impl {
         $(
             impl AsElement for $type {
                 const TYPE: u32 = GL::$gl_type;
+                const RESTART: Self = $type::MAX;
             }
         )*
     };
@@ -49,49 +67,227 @@ as_element_impl! {
     u32, UNSIGNED_INT;
 }
 
+/// Buffer usage hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum Usage {
+    /// Data is loaded once and drawn many times
+    #[default]
+    Static = GL::STATIC_DRAW,
+    /// Data is modified repeatedly and drawn many times
+    Dynamic = GL::DYNAMIC_DRAW,
+    /// Data is modified once and drawn at most a few times
+    Stream = GL::STREAM_DRAW,
+}
+
 pub struct Buffer<G: HasContext, T: BufferTarget> {
     pub(super) buffer: G::Buffer,
     pub(super) length: i32,
+    usage: Usage,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
+    /// CPU copy of indexes
+    #[cfg(feature = "validate")]
+    pub(super) shadow: Option<Vec<u32>>,
     _target: PhantomData<T>,
 }
 
 impl<G: HasContext, T: BufferTarget> Buffer<G, T> {
     /// Create buffer
-    pub fn new(gl: &G) -> Result<Self> {
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G) -> Result<Self> where G: 'static {
         unsafe {
-            let buffer = gl.create_buffer()?;
+            let buffer = gl.create_buffer()
+                .map_err(|error| Error::Create(Object::Buffer, error))?;
             Ok(Self {
                 buffer,
                 length: 0,
+                usage: Usage::Static,
+                queue: DeletionQueue::current(),
+                track: Track::new(Object::Buffer),
+                #[cfg(feature = "validate")]
+                shadow: T::indices(&[]),
                 _target: PhantomData
             })
         }
     }
 
+    /// Attach buffer to deletion queue
+    ///
+    /// Attached buffer is scheduled for deletion when dropped.
+    /// New buffer is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
+    }
+
     /// Remove buffer
-    pub fn del(self, gl: &G) {
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
         unsafe { gl.delete_buffer(self.buffer); }
     }
 
-    /// Set buffer size
+    /// Set usage hint
+    ///
+    /// Hint takes effect on next [`Buffer::load`] or [`Buffer::resize`].
+    pub fn with_usage(mut self, usage: Usage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Usage hint
+    pub fn usage(&self) -> Usage {
+        self.usage
+    }
+
+    /// Buffer length in elements
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    /// Buffer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Set buffer size in elements
+    ///
+    /// NOTE: Existing contents is discarded, use [`Buffer::grow`] to keep it
     pub fn resize(&mut self, gl: &G, size: usize) {
         self.bind_buffer(gl);
         self.length = size as i32;
-        unsafe { gl.buffer_data_size(T::TARGET, self.length, GL::STATIC_DRAW); }
+        unsafe { gl.buffer_data_size(T::TARGET, (size_of::<T::Type>() * size) as i32, self.usage as u32); }
         self.unbind_buffer(gl);
+        #[cfg(feature = "validate")]
+        if let Some(shadow) = &mut self.shadow {
+            shadow.clear();
+            shadow.resize(size, 0);
+        }
     }
 
     /// Load data
     pub fn load(&mut self, gl: &G, data: &[T::Type]) {
         self.bind_buffer(gl);
-        let size = data.len();
+        self.length = data.len() as i32;
+        unsafe { gl.buffer_data_u8_slice(T::TARGET, as_bytes(data), self.usage as u32); }
+        self.unbind_buffer(gl);
+        #[cfg(feature = "validate")]
+        {
+            self.shadow = T::indices(data);
+        }
+    }
+
+    /// Update part of data starting from specified element
+    pub fn update(&mut self, gl: &G, offset: usize, data: &[T::Type]) {
+        let length = self.length as usize;
+        let end = offset + data.len();
+
+        if end > length {
+            panic!("Attempt to exceed the length {} by end of update {}", length, end);
+        }
+
+        self.bind_buffer(gl);
+        unsafe { gl.buffer_sub_data_u8_slice(T::TARGET, (size_of::<T::Type>() * offset) as i32, as_bytes(data)); }
+        self.unbind_buffer(gl);
+        #[cfg(feature = "validate")]
+        if let (Some(shadow), Some(indices)) = (&mut self.shadow, T::indices(data)) {
+            shadow[offset..end].copy_from_slice(&indices);
+        }
+    }
+
+    /// Increase buffer size in elements keeping existing contents
+    ///
+    /// Requires copying between buffers, so fails on OpenGL ES 2.0 and WebGL 1.0.
+    /// Does nothing when buffer already has specified size.
+    pub fn grow(&mut self, gl: &G, size: usize) -> Result<()> {
+        if size <= self.length as usize {
+            return Ok(());
+        }
+
+        let caps = &Current::get(gl).caps;
+        match (caps.api, caps.version) {
+            (Api::Gl, version) if version >= (3, 1) => (),
+            (Api::Gles, version) if version >= (3, 0) => (),
+            (Api::WebGl, version) if version >= (2, 0) => (),
+            _ => return Err(Error::Unsupported("copying buffer data".into())),
+        }
+
+        let old_size = size_of::<T::Type>() * self.length as usize;
+        let new_size = size_of::<T::Type>() * size;
+
+        unsafe {
+            let buffer = gl.create_buffer()
+                .map_err(|error| Error::Create(Object::Buffer, error))?;
+            gl.bind_buffer(GL::COPY_WRITE_BUFFER, Some(buffer));
+            gl.buffer_data_size(GL::COPY_WRITE_BUFFER, new_size as i32, self.usage as u32);
+            gl.bind_buffer(GL::COPY_READ_BUFFER, Some(self.buffer));
+            gl.copy_buffer_sub_data(GL::COPY_READ_BUFFER, GL::COPY_WRITE_BUFFER, 0, 0, old_size as i32);
+            gl.bind_buffer(GL::COPY_READ_BUFFER, None);
+            gl.bind_buffer(GL::COPY_WRITE_BUFFER, None);
+            gl.delete_buffer(self.buffer);
+            self.buffer = buffer;
+        }
+
         self.length = size as i32;
-        let raw: &[u8] = unsafe { from_raw_parts(
-            data as *const _ as *const u8,
-            size_of::<T::Type>() * size,
-        ) };
-        unsafe { gl.buffer_data_u8_slice(T::TARGET, raw, GL::STATIC_DRAW); }
+        #[cfg(feature = "validate")]
+        if let Some(shadow) = &mut self.shadow {
+            shadow.resize(size, 0);
+        }
+
+        Ok(())
+    }
+
+    /// Read data back
+    ///
+    /// Requires OpenGL, OpenGL ES 3.0+ or WebGL 2.0.
+    pub fn read(&self, gl: &G) -> Result<Vec<T::Type>> {
+        let mut data = zeroed_vec(self.length as usize);
+        self.read_into(gl, 0, &mut data)?;
+        Ok(data)
+    }
+
+    /// Read part of data starting from specified element
+    ///
+    /// Uses mapping on OpenGL ES 3.0+ because it doesn't support reading buffers directly.
+    pub fn read_into(&self, gl: &G, offset: usize, data: &mut [T::Type]) -> Result<()> {
+        let length = self.length as usize;
+        let end = offset + data.len();
+
+        if end > length {
+            panic!("Attempt to exceed the length {} by end of read {}", length, end);
+        }
+
+        let caps = &Current::get(gl).caps;
+        let mapping = match (caps.api, caps.version) {
+            (Api::Gl, _) => false,
+            (Api::WebGl, version) if version >= (2, 0) => false,
+            (Api::Gles, version) if version >= (3, 0) => true,
+            _ => return Err(Error::Unsupported("reading buffer data".into())),
+        };
+
+        let offset = (size_of::<T::Type>() * offset) as i32;
+        let raw = as_bytes_mut(data);
+
+        self.bind_buffer(gl);
+        let result = unsafe {
+            if mapping {
+                let ptr = gl.map_buffer_range(T::TARGET, offset, raw.len() as i32, GL::MAP_READ_BIT);
+                if ptr.is_null() {
+                    Err(Error::Unsupported("mapping buffer for reading".into()))
+                } else {
+                    copy_nonoverlapping(ptr as *const u8, raw.as_mut_ptr(), raw.len());
+                    gl.unmap_buffer(T::TARGET);
+                    Ok(())
+                }
+            } else {
+                gl.get_buffer_sub_data(T::TARGET, offset, raw);
+                Ok(())
+            }
+        };
         self.unbind_buffer(gl);
+
+        result
     }
 
     fn bind_buffer(&self, gl: &G) {
@@ -103,6 +299,15 @@ impl<G: HasContext, T: BufferTarget> Buffer<G, T> {
     }
 }
 
+impl<G: HasContext, T: BufferTarget> Drop for Buffer<G, T> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Buffer(self.buffer));
+            self.track.release();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(u32)]
 pub enum DrawMode {
@@ -119,68 +324,303 @@ pub trait Draw<G: HasContext> {
     /// Draw part of attribs array of elements
     fn partial_draw(&self, gl: &G, mode: DrawMode, offset: i32, count: i32);
 
+    /// Draw part of attribs array of elements for each instance
+    fn partial_draw_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32);
+
+    /// Draw part of attribs array of elements with value added to each index
+    fn partial_draw_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32);
+
+    /// Draw several parts of attribs array of elements binding geometry only once
+    fn partial_draw_many(&self, gl: &G, mode: DrawMode, parts: &[(i32, i32)]);
+
+    /// Check that part of elements can be drawn
+    ///
+    /// Checks that context supports instanced drawing and base vertex when it's needed.
+    /// When `validate` feature is enabled also checks that program is bound, buffers matches attributes,
+    /// indexes doesn't exceed vertices and per-instance streams has enough elements.
+    /// The `None` number of instances means default for geometry.
+    fn check_draw(&self, gl: &G, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32) -> Result<()>;
+
     /// The full data length in elements
     fn data_length(&self) -> i32;
 
     /// Draw full
     fn draw(&self, gl: &G, mode: DrawMode) {
-        self.partial_draw(gl, mode, 0, self.data_length());
+        expect_draw(self.try_draw(gl, mode));
+    }
+
+    /// Draw full or get error when data can't be drawn
+    fn try_draw(&self, gl: &G, mode: DrawMode) -> Result<()> {
+        self.try_draw_range(gl, mode, ..)
     }
 
     /// Draw range
     fn draw_range<R: RangeBounds<usize>>(&self, gl: &G, mode: DrawMode, range: R) {
-        use self::Bound::*;
+        expect_draw(self.try_draw_range(gl, mode, range));
+    }
 
-        let length = self.data_length();
+    /// Draw range or get error when data can't be drawn
+    fn try_draw_range<R: RangeBounds<usize>>(&self, gl: &G, mode: DrawMode, range: R) -> Result<()> {
+        let (offset, count) = draw_bounds(&range, self.data_length())?;
+        self.check_draw(gl, offset, count, None, 0)?;
+        self.partial_draw(gl, mode, offset, count);
+        Ok(())
+    }
 
-        let start = match range.start_bound() {
-            Included(start) => *start,
-            Excluded(start) => *start + 1,
-            Unbounded => 0,
-        } as i32;
+    /// Draw range of indexes with value added to each index
+    ///
+    /// Allows to draw meshes from shared buffers without rewriting indexes.
+    /// Requires OpenGL ES 3.2+, OpenGL 3.2+ or `OES_draw_elements_base_vertex` extension
+    /// (see [`Capabilities::has_base_vertex`](super::Capabilities::has_base_vertex)).
+    /// Non-indexed geometry shifts range of vertices instead.
+    fn draw_base_vertex<R: RangeBounds<usize>>(&self, gl: &G, mode: DrawMode, range: R, base_vertex: i32) {
+        expect_draw(self.try_draw_base_vertex(gl, mode, range, base_vertex));
+    }
 
-        if start > length {
-            panic!("Attempt to exceed the length {} by start bound {}", length, start);
-        }
+    /// Draw range of indexes with value added to each index or get error when data can't be drawn
+    fn try_draw_base_vertex<R: RangeBounds<usize>>(&self, gl: &G, mode: DrawMode, range: R, base_vertex: i32) -> Result<()> {
+        let (offset, count) = draw_bounds(&range, self.data_length())?;
+        self.check_draw(gl, offset, count, None, base_vertex)?;
+        self.partial_draw_base_vertex(gl, mode, offset, count, base_vertex);
+        Ok(())
+    }
 
-        let end = match range.end_bound() {
-            Included(end) => *end as i32 + 1,
-            Excluded(end) => *end as i32,
-            Unbounded => start + length,
-        };
+    /// Draw full for each instance
+    ///
+    /// Requires OpenGL ES 3.0, OpenGL 3.3 or `ANGLE_instanced_arrays` extension
+    /// (see [`Capabilities::has_instancing`](super::Capabilities::has_instancing)).
+    fn draw_instanced(&self, gl: &G, mode: DrawMode, instances: usize) {
+        expect_draw(self.try_draw_instanced(gl, mode, instances));
+    }
 
-        if end > length {
-            panic!("Attempt to exceed the length {} by end bound {}", length, start);
+    /// Draw full for each instance or get error when data can't be drawn
+    fn try_draw_instanced(&self, gl: &G, mode: DrawMode, instances: usize) -> Result<()> {
+        let count = self.data_length();
+        self.check_draw(gl, 0, count, Some(instances as i32), 0)?;
+        self.partial_draw_instanced(gl, mode, 0, count, instances as i32);
+        Ok(())
+    }
+
+    /// Draw several ranges binding geometry only once
+    fn draw_many(&self, gl: &G, mode: DrawMode, ranges: &[Range<usize>]) {
+        expect_draw(self.try_draw_many(gl, mode, ranges));
+    }
+
+    /// Draw several ranges or get error when data can't be drawn
+    ///
+    /// Nothing is drawn when some of ranges can't be drawn.
+    fn try_draw_many(&self, gl: &G, mode: DrawMode, ranges: &[Range<usize>]) -> Result<()> {
+        let length = self.data_length();
+        let parts = ranges.iter()
+            .map(|range| draw_bounds(range, length))
+            .collect::<Result<Vec<_>>>()?;
+        for (offset, count) in &parts {
+            self.check_draw(gl, *offset, *count, None, 0)?;
         }
+        self.partial_draw_many(gl, mode, &parts);
+        Ok(())
+    }
+}
 
-        self.partial_draw(gl, mode, start, end - start);
+fn expect_draw(result: Result<()>) {
+    if let Err(error) = result {
+        panic!("{}", error);
     }
 }
 
-impl<G: HasContext, A: Attribs<G>, T> Draw<G> for (Buffer<G, Array<T>>, A) {
+/// Convert range to offset and count checking it against length
+fn draw_bounds<R: RangeBounds<usize>>(range: &R, length: i32) -> Result<(i32, i32)> {
+    use self::Bound::*;
+
+    let start = match range.start_bound() {
+        Included(start) => *start,
+        Excluded(start) => *start + 1,
+        Unbounded => 0,
+    } as i32;
+
+    if start > length {
+        return Err(Error::Draw(format!("attempt to exceed the length {} by start bound {}", length, start)));
+    }
+
+    let end = match range.end_bound() {
+        Included(end) => *end as i32 + 1,
+        Excluded(end) => *end as i32,
+        Unbounded => length,
+    };
+
+    if end > length {
+        return Err(Error::Draw(format!("attempt to exceed the length {} by end bound {}", length, end)));
+    }
+
+    if end < start {
+        return Err(Error::Draw(format!("end bound {} is less than start bound {}", end, start)));
+    }
+
+    Ok((start, end - start))
+}
+
+/// The trait for geometry which can be drawn
+///
+/// Implementing this trait makes [`Draw`] methods available.
+pub trait Geometry<G: HasContext> {
+    /// Bind buffers and enable attributes
+    fn bind(&self, gl: &G);
+
+    /// Disable attributes and unbind buffers
+    fn unbind(&self, gl: &G);
+
+    /// Draw part of elements using bound state
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32);
+
+    /// Draw part of elements for each instance using bound state
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32);
+
+    /// Draw part of elements with value added to each index using bound state
+    ///
+    /// Non-indexed geometry shifts range of vertices by default.
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        self.draw_bound(gl, mode, offset + base_vertex, count);
+    }
+
+    /// Draw part of elements for each instance with value added to each index using bound state
+    ///
+    /// Non-indexed geometry shifts range of vertices by default.
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        self.draw_bound_instanced(gl, mode, offset + base_vertex, count, instances);
+    }
+
+    /// The full data length in elements
+    fn length(&self) -> i32;
+
+    /// Geometry is always drawn instanced
+    fn is_instanced(&self) -> bool {
+        false
+    }
+
+    /// Geometry is drawn using indexes
+    fn is_indexed(&self) -> bool {
+        false
+    }
+
+    /// Check that part of elements can be drawn with current data
+    ///
+    /// Non-indexed geometry shifts range of vertices by base vertex.
+    /// Restart index is skipped when `restart` is set by [`PrimitiveRestart`](super::PrimitiveRestart).
+    #[cfg(feature = "validate")]
+    #[allow(unused_variables)]
+    fn validate(&self, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32, restart: bool) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<G: HasContext, D: Geometry<G>> Draw<G> for D {
     fn partial_draw(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        self.bind(gl);
+        self.draw_bound(gl, mode, offset, count);
+        self.unbind(gl);
+    }
+    fn partial_draw_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        self.bind(gl);
+        self.draw_bound_instanced(gl, mode, offset, count, instances);
+        self.unbind(gl);
+    }
+    fn partial_draw_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        self.bind(gl);
+        self.draw_bound_base_vertex(gl, mode, offset, count, base_vertex);
+        self.unbind(gl);
+    }
+    fn partial_draw_many(&self, gl: &G, mode: DrawMode, parts: &[(i32, i32)]) {
+        self.bind(gl);
+        for (offset, count) in parts {
+            self.draw_bound(gl, mode, *offset, *count);
+        }
+        self.unbind(gl);
+    }
+    #[allow(unused_variables)]
+    fn check_draw(&self, gl: &G, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32) -> Result<()> {
+        let current = Current::get(gl);
+        let instanced = instances.is_some() || self.is_instanced();
+        if instanced && !current.has_instancing() {
+            return Err(Error::Unsupported("instanced drawing".into()));
+        }
+        if base_vertex != 0 && self.is_indexed() && !current.has_base_vertex(instanced) {
+            return Err(Error::Unsupported("drawing with base vertex".into()));
+        }
+        #[cfg(feature = "validate")]
+        {
+            validate::check_program(gl)?;
+            self.validate(offset, count, instances, base_vertex, false)?;
+        }
+        Ok(())
+    }
+    fn data_length(&self) -> i32 {
+        self.length()
+    }
+}
+
+impl<G: HasContext, A: Attribs<G>, T: Pod> Geometry<G> for (Buffer<G, Array<T>>, A) {
+    fn bind(&self, gl: &G) {
         self.0.bind_buffer(gl);
         self.1.enable_attribs(gl);
-        unsafe { gl.draw_arrays(mode as u32, offset, count); }
+    }
+    fn unbind(&self, gl: &G) {
         self.1.disable_attribs(gl);
         self.0.unbind_buffer(gl);
     }
-    fn data_length(&self) -> i32 {
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        unsafe { gl.draw_arrays(mode as u32, offset, count); }
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        unsafe { Current::get(gl).draw_arrays_instanced(gl, mode as u32, offset, count, instances); }
+    }
+    fn length(&self) -> i32 {
         self.0.length
     }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, _instances: Option<i32>, base_vertex: i32, _restart: bool) -> Result<()> {
+        let vertices = validate::vertices::<T>(self.0.length, self.1.stride())?;
+        validate::check_vertices(offset + base_vertex, count, vertices)
+    }
 }
 
-impl<G: HasContext, A: Attribs<G>, T, I: AsElement> Draw<G> for (Buffer<G, Array<T>>, A, Buffer<G, ElementArray<I>>) {
-    fn partial_draw(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+impl<G: HasContext, A: Attribs<G>, T: Pod, I: AsElement> Geometry<G> for (Buffer<G, Array<T>>, A, Buffer<G, ElementArray<I>>) {
+    fn bind(&self, gl: &G) {
         self.0.bind_buffer(gl);
         self.2.bind_buffer(gl);
         self.1.enable_attribs(gl);
-        unsafe { gl.draw_elements(mode as u32, count, I::TYPE, offset); }
+    }
+    fn unbind(&self, gl: &G) {
         self.1.disable_attribs(gl);
-        self.2.bind_buffer(gl);
-        self.0.bind_buffer(gl);
+        self.2.unbind_buffer(gl);
+        self.0.unbind_buffer(gl);
     }
-    fn data_length(&self) -> i32 {
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        unsafe { gl.draw_elements(mode as u32, count, I::TYPE, element_offset::<I>(offset)); }
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        unsafe { Current::get(gl).draw_elements_instanced::<G, I>(gl, mode as u32, offset, count, instances); }
+    }
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        unsafe { Current::get(gl).draw_elements_base_vertex::<G, I>(gl, mode as u32, offset, count, base_vertex); }
+    }
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        unsafe { Current::get(gl).draw_elements_instanced_base_vertex::<G, I>(gl, mode as u32, offset, count, instances, base_vertex); }
+    }
+    fn length(&self) -> i32 {
         self.2.length
     }
+    fn is_indexed(&self) -> bool {
+        true
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, _instances: Option<i32>, base_vertex: i32, restart: bool) -> Result<()> {
+        let vertices = validate::vertices::<T>(self.0.length, self.1.stride())?;
+        let indices = self.2.shadow.as_deref().unwrap_or_default();
+        validate::check_indices(indices, offset, count, restart.then(|| I::RESTART.into()), base_vertex, vertices)
+    }
+}
+
+/// Convert offset in elements to offset in bytes
+pub(super) fn element_offset<I: AsElement>(offset: i32) -> i32 {
+    offset * size_of::<I>() as i32
 }
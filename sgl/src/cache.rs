@@ -0,0 +1,151 @@
+use std::{
+    fs,
+    path::PathBuf,
+    convert::TryInto,
+};
+use super::{Result, Error, Object, GL, HasContext, ShaderType, Shader, Program, context::Current};
+
+/// On-disk cache of linked program binaries
+///
+/// Binaries is keyed by hash of shader sources and GL vendor, renderer and version strings,
+/// so cache is invalidated automatically when driver changes.
+/// When context doesn't support program binaries the cache simply compiles programs from sources.
+///
+/// Cache is available only on native targets, because program binaries entry points
+/// operates on raw program names which WebGL doesn't expose.
+#[derive(Debug, Clone)]
+pub struct ProgramCache {
+    path: PathBuf,
+    context: String,
+    supported: bool,
+}
+
+impl ProgramCache {
+    /// Create cache which stores binaries in specified directory
+    ///
+    /// Program binaries requires entry points which is loaded by [`make_current`](super::make_current).
+    pub fn new<G: HasContext, P: Into<PathBuf>>(gl: &G, path: P) -> Self {
+        let current = Current::get(gl);
+        let caps = &current.caps;
+
+        let supported = current.has_program_binary() &&
+            unsafe { gl.get_parameter_i32(GL::NUM_PROGRAM_BINARY_FORMATS) } > 0;
+
+        let context = format!("{}\0{}\0{}", caps.vendor, caps.renderer, caps.version_string);
+
+        Self { path: path.into(), context, supported }
+    }
+
+    /// Context supports program binaries
+    pub fn is_supported(&self) -> bool {
+        self.supported
+    }
+
+    /// Get program from cache or compile it using specified sources
+    ///
+    /// When cached binary cannot be loaded for any reason the program is compiled from sources and cached again.
+    /// I/O errors is ignored, because cache is only an optimization.
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn program<G: HasContext<Program = u32> + 'static, S: AsRef<str>>(&self, gl: &G, sources: &[(ShaderType, S)]) -> Result<Program<G>> {
+        if !self.supported {
+            return Self::compile(gl, sources, false);
+        }
+
+        let path = self.path.join(format!("{:016x}.bin", self.key(sources)));
+
+        if let Ok(data) = fs::read(&path) {
+            if let Ok(Some(program)) = Self::load(gl, &data) {
+                return Ok(program);
+            }
+            let _ = fs::remove_file(&path);
+        }
+
+        let program = Self::compile(gl, sources, true)?;
+
+        if let Ok((format, buffer)) = unsafe { Current::get(gl).get_program_binary(program.program) } {
+            let mut data = Vec::with_capacity(4 + buffer.len());
+            data.extend_from_slice(&format.to_le_bytes());
+            data.extend_from_slice(&buffer);
+            let _ = fs::create_dir_all(&self.path)
+                .and_then(|_| fs::write(&path, data));
+        }
+
+        Ok(program)
+    }
+
+    /// Remove all cached binaries
+    pub fn clear(&self) {
+        if let Ok(entries) = fs::read_dir(&self.path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().map(|ext| ext == "bin").unwrap_or(false) {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
+    }
+
+    fn key<S: AsRef<str>>(&self, sources: &[(ShaderType, S)]) -> u64 {
+        let mut hash = Fnv::new();
+        hash.write(self.context.as_bytes());
+        for (typ, source) in sources {
+            hash.write(&(*typ as u32).to_le_bytes());
+            hash.write(source.as_ref().as_bytes());
+            hash.write(&[0]);
+        }
+        hash.finish()
+    }
+
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    fn compile<G: HasContext<Program = u32> + 'static, S: AsRef<str>>(gl: &G, sources: &[(ShaderType, S)], retrievable: bool) -> Result<Program<G>> {
+        Program::link(gl, Shader::new_all(gl, sources)?, |program| if retrievable {
+            unsafe { Current::get(gl).program_binary_retrievable_hint(program) }
+        } else {
+            Ok(())
+        })
+    }
+
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    fn load<G: HasContext<Program = u32> + 'static>(gl: &G, data: &[u8]) -> Result<Option<Program<G>>> {
+        if data.len() < 4 {
+            return Ok(None);
+        }
+
+        let format = u32::from_le_bytes(data[..4].try_into().unwrap());
+
+        unsafe {
+            let program = gl.create_program()
+                .map_err(|error| Error::Create(Object::Program, error))?;
+            if let Err(error) = Current::get(gl).program_binary(program, format, &data[4..]) {
+                gl.delete_program(program);
+                return Err(error);
+            }
+            if gl.get_program_link_status(program) {
+                Ok(Some(Program::from_raw(program)))
+            } else {
+                gl.delete_program(program);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// FNV-1a hash
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use super::{GL, HasContext, Dialect};
+
+/// OpenGL API flavor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Api {
+    /// Desktop OpenGL
+    Gl,
+    /// OpenGL ES
+    Gles,
+    /// WebGL
+    WebGl,
+}
+
+/// Capabilities of current context
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    /// API flavor
+    pub api: Api,
+    /// API version (major, minor)
+    pub version: (u8, u8),
+    /// Value of `GL_VENDOR`
+    pub vendor: String,
+    /// Value of `GL_RENDERER`
+    pub renderer: String,
+    /// Value of `GL_VERSION`
+    pub version_string: String,
+    /// Supported extensions (without `GL_` prefix)
+    pub extensions: HashSet<String>,
+}
+
+impl Capabilities {
+    /// Query capabilities of context
+    pub fn query<G: HasContext>(gl: &G) -> Self {
+        let vendor = unsafe { gl.get_parameter_string(GL::VENDOR) };
+        let renderer = unsafe { gl.get_parameter_string(GL::RENDERER) };
+        let version_string = unsafe { gl.get_parameter_string(GL::VERSION) };
+        let (api, version) = Self::parse_version(&version_string);
+
+        // GL_EXTENSIONS string isn't available in core profiles
+        let extensions = if api != Api::WebGl && version.0 >= 3 {
+            let count = unsafe { gl.get_parameter_i32(GL::NUM_EXTENSIONS) };
+            (0..count as u32)
+                .map(|index| unsafe { gl.get_parameter_indexed_string(GL::EXTENSIONS, index) })
+                .map(|name| strip_prefix(&name).into())
+                .collect()
+        } else {
+            unsafe { gl.get_parameter_string(GL::EXTENSIONS) }
+                .split_whitespace()
+                .map(|name| strip_prefix(name).into())
+                .collect()
+        };
+
+        Self { api, version, vendor, renderer, version_string, extensions }
+    }
+
+    /// Parse `GL_VERSION` string
+    ///
+    /// Understands `OpenGL ES 3.2 ...`, `WebGL 1.0 ...` and desktop `4.6.0 ...` formats.
+    pub fn parse_version(version: &str) -> (Api, (u8, u8)) {
+        let (api, rest) = if let Some(rest) = version.strip_prefix("OpenGL ES") {
+            // skip profile suffix like `-CM`
+            (Api::Gles, rest.trim_start_matches(|c: char| !c.is_whitespace()))
+        } else if let Some(rest) = version.strip_prefix("WebGL") {
+            (Api::WebGl, rest)
+        } else {
+            (Api::Gl, version)
+        };
+
+        let mut numbers = rest.trim_start()
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|number| number.parse().unwrap_or(0));
+
+        let major = numbers.next().unwrap_or(0);
+        let minor = numbers.next().unwrap_or(0);
+
+        (api, (major, minor))
+    }
+
+    /// Check that API version is at least specified
+    pub fn is_version(&self, api: Api, major: u8, minor: u8) -> bool {
+        self.api == api && self.version >= (major, minor)
+    }
+
+    /// Check that extension is supported
+    ///
+    /// Name may be specified both with and without `GL_` prefix.
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.contains(strip_prefix(name))
+    }
+
+    /// Check that vertex array objects is supported
+    pub fn has_vertex_array(&self) -> bool {
+        self.is_version(Api::Gl, 3, 0) ||
+            self.is_version(Api::Gles, 3, 0) ||
+            self.is_version(Api::WebGl, 2, 0) ||
+            self.has_extension("ARB_vertex_array_object") ||
+            self.has_extension("OES_vertex_array_object")
+    }
+
+    /// Check that instanced drawing and per-instance attributes is supported
+    pub fn has_instancing(&self) -> bool {
+        self.is_version(Api::Gl, 3, 3) ||
+            self.is_version(Api::Gles, 3, 0) ||
+            self.is_version(Api::WebGl, 2, 0) ||
+            self.has_extension("ANGLE_instanced_arrays") ||
+            self.has_extension("EXT_instanced_arrays") ||
+            self.has_extension("ARB_instanced_arrays") && self.has_extension("ARB_draw_instanced")
+    }
+
+    /// Check that indexes can be drawn with base vertex
+    pub fn has_base_vertex(&self) -> bool {
+        self.is_version(Api::Gl, 3, 2) ||
+            self.is_version(Api::Gles, 3, 2) ||
+            self.has_extension("OES_draw_elements_base_vertex") ||
+            self.has_extension("EXT_draw_elements_base_vertex")
+    }
+
+    /// Check that ETC1 compressed textures is supported
+    pub fn has_etc1(&self) -> bool {
+        self.has_extension("OES_compressed_ETC1_RGB8_texture") ||
+            self.has_extension("WEBGL_compressed_texture_etc1")
+    }
+
+    /// Check that ETC2 and EAC compressed textures is supported
+    pub fn has_etc2(&self) -> bool {
+        self.is_version(Api::Gles, 3, 0) ||
+            self.is_version(Api::Gl, 4, 3) ||
+            self.has_extension("ARB_ES3_compatibility") ||
+            self.has_extension("WEBGL_compressed_texture_etc")
+    }
+
+    /// Check that ASTC compressed textures is supported
+    pub fn has_astc(&self) -> bool {
+        self.is_version(Api::Gles, 3, 2) ||
+            self.has_extension("KHR_texture_compression_astc_ldr") ||
+            self.has_extension("WEBGL_compressed_texture_astc")
+    }
+
+    /// Get GLSL dialect which is suitable for context
+    pub fn dialect(&self) -> Dialect {
+        Dialect::new(self.api, self.version)
+    }
+}
+
+fn strip_prefix(name: &str) -> &str {
+    name.strip_prefix("GL_").unwrap_or(name)
+}
@@ -0,0 +1,116 @@
+use super::{GL, TextureFormat, Capabilities};
+
+/// Block of 64 bits of compressed data
+pub type Block64 = [u8; 8];
+
+/// Block of 128 bits of compressed data
+pub type Block128 = [u8; 16];
+
+const ETC1_RGB8_OES: u32 = 0x8D64;
+
+macro_rules! compressed_formats {
+    ($($(#[$meta: meta])* $name: ident, $format: expr, $block: ty, $width: literal x $height: literal, $supported: ident;)*) => {
+        $(
+            $(#[$meta])*
+            #[allow(non_camel_case_types)]
+            pub struct $name;
+
+            impl TextureFormat for $name {
+                type Pixel = $block;
+                const FORMAT: u32 = $format;
+                const TYPE: u32 = 0;
+                const BLOCK: (usize, usize) = ($width, $height);
+                const OPTIONAL: bool = true;
+                const COMPRESSED: bool = true;
+
+                fn is_supported(caps: &Capabilities) -> bool {
+                    caps.$supported()
+                }
+            }
+        )*
+    };
+}
+
+compressed_formats! {
+    /// ETC1 RGB (OpenGL ES 2.0 with `OES_compressed_ETC1_RGB8_texture`)
+    ETC1_RGB8, ETC1_RGB8_OES, Block64, 4 x 4, has_etc1;
+
+    /// ETC2 RGB
+    ETC2_RGB8, GL::COMPRESSED_RGB8_ETC2, Block64, 4 x 4, has_etc2;
+    /// ETC2 sRGB
+    ETC2_SRGB8, GL::COMPRESSED_SRGB8_ETC2, Block64, 4 x 4, has_etc2;
+    /// ETC2 RGB with 1-bit alpha
+    ETC2_RGB8_A1, GL::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2, Block64, 4 x 4, has_etc2;
+    /// ETC2 sRGB with 1-bit alpha
+    ETC2_SRGB8_A1, GL::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2, Block64, 4 x 4, has_etc2;
+    /// ETC2 RGBA
+    ETC2_RGBA8, GL::COMPRESSED_RGBA8_ETC2_EAC, Block128, 4 x 4, has_etc2;
+    /// ETC2 sRGB with alpha
+    ETC2_SRGB8_ALPHA8, GL::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC, Block128, 4 x 4, has_etc2;
+    /// EAC unsigned red
+    EAC_R11, GL::COMPRESSED_R11_EAC, Block64, 4 x 4, has_etc2;
+    /// EAC signed red
+    EAC_SIGNED_R11, GL::COMPRESSED_SIGNED_R11_EAC, Block64, 4 x 4, has_etc2;
+    /// EAC unsigned red and green
+    EAC_RG11, GL::COMPRESSED_RG11_EAC, Block128, 4 x 4, has_etc2;
+    /// EAC signed red and green
+    EAC_SIGNED_RG11, GL::COMPRESSED_SIGNED_RG11_EAC, Block128, 4 x 4, has_etc2;
+
+    /// ASTC RGBA with 4x4 blocks
+    ASTC_4x4, 0x93B0, Block128, 4 x 4, has_astc;
+    /// ASTC RGBA with 5x4 blocks
+    ASTC_5x4, 0x93B1, Block128, 5 x 4, has_astc;
+    /// ASTC RGBA with 5x5 blocks
+    ASTC_5x5, 0x93B2, Block128, 5 x 5, has_astc;
+    /// ASTC RGBA with 6x5 blocks
+    ASTC_6x5, 0x93B3, Block128, 6 x 5, has_astc;
+    /// ASTC RGBA with 6x6 blocks
+    ASTC_6x6, 0x93B4, Block128, 6 x 6, has_astc;
+    /// ASTC RGBA with 8x5 blocks
+    ASTC_8x5, 0x93B5, Block128, 8 x 5, has_astc;
+    /// ASTC RGBA with 8x6 blocks
+    ASTC_8x6, 0x93B6, Block128, 8 x 6, has_astc;
+    /// ASTC RGBA with 8x8 blocks
+    ASTC_8x8, 0x93B7, Block128, 8 x 8, has_astc;
+    /// ASTC RGBA with 10x5 blocks
+    ASTC_10x5, 0x93B8, Block128, 10 x 5, has_astc;
+    /// ASTC RGBA with 10x6 blocks
+    ASTC_10x6, 0x93B9, Block128, 10 x 6, has_astc;
+    /// ASTC RGBA with 10x8 blocks
+    ASTC_10x8, 0x93BA, Block128, 10 x 8, has_astc;
+    /// ASTC RGBA with 10x10 blocks
+    ASTC_10x10, 0x93BB, Block128, 10 x 10, has_astc;
+    /// ASTC RGBA with 12x10 blocks
+    ASTC_12x10, 0x93BC, Block128, 12 x 10, has_astc;
+    /// ASTC RGBA with 12x12 blocks
+    ASTC_12x12, 0x93BD, Block128, 12 x 12, has_astc;
+
+    /// ASTC sRGB with alpha and 4x4 blocks
+    ASTC_SRGB_4x4, 0x93D0, Block128, 4 x 4, has_astc;
+    /// ASTC sRGB with alpha and 5x4 blocks
+    ASTC_SRGB_5x4, 0x93D1, Block128, 5 x 4, has_astc;
+    /// ASTC sRGB with alpha and 5x5 blocks
+    ASTC_SRGB_5x5, 0x93D2, Block128, 5 x 5, has_astc;
+    /// ASTC sRGB with alpha and 6x5 blocks
+    ASTC_SRGB_6x5, 0x93D3, Block128, 6 x 5, has_astc;
+    /// ASTC sRGB with alpha and 6x6 blocks
+    ASTC_SRGB_6x6, 0x93D4, Block128, 6 x 6, has_astc;
+    /// ASTC sRGB with alpha and 8x5 blocks
+    ASTC_SRGB_8x5, 0x93D5, Block128, 8 x 5, has_astc;
+    /// ASTC sRGB with alpha and 8x6 blocks
+    ASTC_SRGB_8x6, 0x93D6, Block128, 8 x 6, has_astc;
+    /// ASTC sRGB with alpha and 8x8 blocks
+    ASTC_SRGB_8x8, 0x93D7, Block128, 8 x 8, has_astc;
+    /// ASTC sRGB with alpha and 10x5 blocks
+    ASTC_SRGB_10x5, 0x93D8, Block128, 10 x 5, has_astc;
+    /// ASTC sRGB with alpha and 10x6 blocks
+    ASTC_SRGB_10x6, 0x93D9, Block128, 10 x 6, has_astc;
+    /// ASTC sRGB with alpha and 10x8 blocks
+    ASTC_SRGB_10x8, 0x93DA, Block128, 10 x 8, has_astc;
+    /// ASTC sRGB with alpha and 10x10 blocks
+    ASTC_SRGB_10x10, 0x93DB, Block128, 10 x 10, has_astc;
+    /// ASTC sRGB with alpha and 12x10 blocks
+    ASTC_SRGB_12x10, 0x93DC, Block128, 12 x 10, has_astc;
+    /// ASTC sRGB with alpha and 12x12 blocks
+    ASTC_SRGB_12x12, 0x93DD, Block128, 12 x 12, has_astc;
+}
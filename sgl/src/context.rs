@@ -0,0 +1,399 @@
+use std::{
+    cell::RefCell,
+    ffi::c_void,
+    mem::transmute,
+    rc::Rc,
+};
+use super::{Result, Error, Object, GL, HasContext, Api, Capabilities, AsElement, element_offset, queue};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Rc<Current>>> = const { RefCell::new(None) };
+}
+
+/// Make context current for this thread
+///
+/// Queries capabilities of context and loads entry points which glow doesn't provide
+/// (program binaries, compressed textures, vertex array objects, instancing and base vertex extensions) using specified loader (usually `get_proc_address` of windowing library).
+/// Should be called each time when another context becomes current.
+///
+/// When no context is made current explicitly the capabilities is queried on first use
+/// and functionality which requires additional entry points is reported as unsupported.
+pub fn make_current<G: HasContext, F: FnMut(&str) -> *const c_void>(gl: &G, mut loader: F) {
+    let caps = Capabilities::query(gl);
+    let procs = Procs::load(&caps, &mut loader);
+    CURRENT.with(|current| *current.borrow_mut() = Some(Rc::new(Current { caps, procs })));
+}
+
+/// Forget current context and deletion queue of this thread
+pub fn release_current() {
+    CURRENT.with(|current| *current.borrow_mut() = None);
+    queue::release_current();
+}
+
+/// State of current context
+pub(super) struct Current {
+    pub caps: Capabilities,
+    procs: Procs,
+}
+
+impl Current {
+    /// Get state of current context
+    pub fn get<G: HasContext>(gl: &G) -> Rc<Self> {
+        CURRENT.with(|current| current.borrow_mut()
+                     .get_or_insert_with(|| Rc::new(Current {
+                         caps: Capabilities::query(gl),
+                         procs: Procs::default(),
+                     }))
+                     .clone())
+    }
+
+    /// Program binaries can be retrieved and loaded
+    pub fn has_program_binary(&self) -> bool {
+        self.procs.get_program_binary.is_some() && self.procs.program_binary.is_some()
+    }
+
+    /// Set `PROGRAM_BINARY_RETRIEVABLE_HINT` parameter of program before linking
+    pub unsafe fn program_binary_retrievable_hint(&self, program: u32) -> Result<()> {
+        // OES_get_program_binary has no such parameter, binaries is always retrievable
+        if let Some(program_parameter) = self.procs.program_parameter_i32 {
+            program_parameter(program, GL::PROGRAM_BINARY_RETRIEVABLE_HINT, GL::TRUE as i32);
+        } else if !self.has_program_binary() {
+            return Err(unsupported("program binaries"));
+        }
+        Ok(())
+    }
+
+    /// Get format and data of linked program binary
+    pub unsafe fn get_program_binary(&self, program: u32) -> Result<(u32, Vec<u8>)> {
+        let (get_program_iv, get_program_binary) = match (self.procs.get_program_iv, self.procs.get_program_binary) {
+            (Some(get_program_iv), Some(get_program_binary)) => (get_program_iv, get_program_binary),
+            _ => return Err(unsupported("program binaries")),
+        };
+
+        let mut length = 0;
+        get_program_iv(program, GL::PROGRAM_BINARY_LENGTH, &mut length);
+
+        let mut data = vec![0u8; length.max(0) as usize];
+        let mut format = 0;
+        let mut written = 0;
+        get_program_binary(program, length, &mut written, &mut format, data.as_mut_ptr() as *mut c_void);
+        data.truncate(written.max(0) as usize);
+
+        Ok((format, data))
+    }
+
+    /// Load program from binary
+    pub unsafe fn program_binary(&self, program: u32, format: u32, data: &[u8]) -> Result<()> {
+        let program_binary = self.procs.program_binary
+            .ok_or_else(|| unsupported("program binaries"))?;
+        program_binary(program, format, data.as_ptr() as *const c_void, data.len() as i32);
+        Ok(())
+    }
+
+    /// Instanced drawing is supported and entry points is available
+    pub fn has_instancing(&self) -> bool {
+        has_core_instancing(&self.caps) || (
+            self.procs.draw_arrays_instanced.is_some() &&
+                self.procs.draw_elements_instanced.is_some() &&
+                self.procs.vertex_attrib_divisor.is_some()
+        )
+    }
+
+    /// Draw arrays for each instance
+    ///
+    /// Panics when instancing isn't supported, use [`Current::has_instancing`] to check it first.
+    pub unsafe fn draw_arrays_instanced<G: HasContext>(&self, gl: &G, mode: u32, first: i32, count: i32, instances: i32) {
+        if has_core_instancing(&self.caps) {
+            gl.draw_arrays_instanced(mode, first, count, instances);
+        } else if let Some(draw_arrays_instanced) = self.procs.draw_arrays_instanced {
+            draw_arrays_instanced(mode, first, count, instances);
+        } else {
+            panic!("{}", unsupported("instanced drawing"));
+        }
+    }
+
+    /// Draw elements for each instance
+    ///
+    /// Panics when instancing isn't supported, use [`Current::has_instancing`] to check it first.
+    pub unsafe fn draw_elements_instanced<G: HasContext, I: AsElement>(&self, gl: &G, mode: u32, offset: i32, count: i32, instances: i32) {
+        let offset = element_offset::<I>(offset);
+        if has_core_instancing(&self.caps) {
+            gl.draw_elements_instanced(mode, count, I::TYPE, offset, instances);
+        } else if let Some(draw_elements_instanced) = self.procs.draw_elements_instanced {
+            draw_elements_instanced(mode, count, I::TYPE, offset as usize as *const c_void, instances);
+        } else {
+            panic!("{}", unsupported("instanced drawing"));
+        }
+    }
+
+    /// Indexes can be drawn with base vertex and entry points is available
+    ///
+    /// Instanced drawing with base vertex requires additional entry point.
+    pub fn has_base_vertex(&self, instanced: bool) -> bool {
+        has_core_base_vertex(&self.caps) || (
+            self.procs.draw_elements_base_vertex.is_some() &&
+                (!instanced || self.procs.draw_elements_instanced_base_vertex.is_some())
+        )
+    }
+
+    /// Draw elements with value added to each index
+    ///
+    /// Zero base vertex is drawn as usual.
+    /// Panics when base vertex isn't supported, use [`Current::has_base_vertex`] to check it first.
+    pub unsafe fn draw_elements_base_vertex<G: HasContext, I: AsElement>(&self, gl: &G, mode: u32, offset: i32, count: i32, base_vertex: i32) {
+        let offset = element_offset::<I>(offset);
+        if base_vertex == 0 {
+            gl.draw_elements(mode, count, I::TYPE, offset);
+        } else if has_core_base_vertex(&self.caps) {
+            gl.draw_elements_base_vertex(mode, count, I::TYPE, offset, base_vertex);
+        } else if let Some(draw_elements_base_vertex) = self.procs.draw_elements_base_vertex {
+            draw_elements_base_vertex(mode, count, I::TYPE, offset as usize as *const c_void, base_vertex);
+        } else {
+            panic!("{}", unsupported("drawing with base vertex"));
+        }
+    }
+
+    /// Draw elements for each instance with value added to each index
+    ///
+    /// Zero base vertex is drawn as usual.
+    /// Panics when base vertex isn't supported, use [`Current::has_base_vertex`] to check it first.
+    pub unsafe fn draw_elements_instanced_base_vertex<G: HasContext, I: AsElement>(&self, gl: &G, mode: u32, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        if base_vertex == 0 {
+            self.draw_elements_instanced::<G, I>(gl, mode, offset, count, instances);
+        } else if has_core_base_vertex(&self.caps) {
+            gl.draw_elements_instanced_base_vertex(mode, count, I::TYPE, element_offset::<I>(offset), instances, base_vertex);
+        } else if let Some(draw_elements_instanced_base_vertex) = self.procs.draw_elements_instanced_base_vertex {
+            draw_elements_instanced_base_vertex(mode, count, I::TYPE, element_offset::<I>(offset) as usize as *const c_void, instances, base_vertex);
+        } else {
+            panic!("{}", unsupported("drawing with base vertex"));
+        }
+    }
+
+    /// Set divisor of attribute
+    ///
+    /// Zero divisor is default, so it's ignored when instancing isn't supported.
+    /// Panics on non-zero divisor when instancing isn't supported.
+    pub unsafe fn vertex_attrib_divisor<G: HasContext>(&self, gl: &G, index: u32, divisor: u32) {
+        if has_core_instancing(&self.caps) {
+            gl.vertex_attrib_divisor(index, divisor);
+        } else if let Some(vertex_attrib_divisor) = self.procs.vertex_attrib_divisor {
+            vertex_attrib_divisor(index, divisor);
+        } else if divisor != 0 {
+            panic!("{}", unsupported("instanced drawing"));
+        }
+    }
+
+    /// Vertex array objects is supported and entry points is available
+    pub fn has_vertex_array(&self) -> bool {
+        has_core_vertex_array(&self.caps) || (
+            self.procs.gen_vertex_arrays.is_some() &&
+                self.procs.bind_vertex_array.is_some() &&
+                self.procs.delete_vertex_arrays.is_some()
+        )
+    }
+
+    /// Create vertex array object
+    pub unsafe fn create_vertex_array<G: HasContext>(&self, gl: &G) -> Result<VertexArrayObject<G>> {
+        if has_core_vertex_array(&self.caps) {
+            gl.create_vertex_array()
+                .map(VertexArrayObject::Core)
+                .map_err(|error| Error::Create(Object::VertexArray, error))
+        } else if let Some(gen_vertex_arrays) = self.procs.gen_vertex_arrays {
+            let mut name = 0;
+            gen_vertex_arrays(1, &mut name);
+            if name == 0 {
+                Err(Error::Create(Object::VertexArray, "no name was generated".into()))
+            } else {
+                Ok(VertexArrayObject::Oes(name))
+            }
+        } else {
+            Err(unsupported("vertex array objects"))
+        }
+    }
+
+    /// Bind vertex array object or unbind it when `None` is passed
+    pub unsafe fn bind_vertex_array<G: HasContext>(&self, gl: &G, vertex_array: Option<&VertexArrayObject<G>>) {
+        match vertex_array {
+            Some(VertexArrayObject::Core(vertex_array)) => gl.bind_vertex_array(Some(*vertex_array)),
+            Some(VertexArrayObject::Oes(name)) => self.bind_vertex_array_oes(*name),
+            None if has_core_vertex_array(&self.caps) => gl.bind_vertex_array(None),
+            None => self.bind_vertex_array_oes(0),
+        }
+    }
+
+    /// Delete vertex array object of `OES_vertex_array_object` extension
+    pub unsafe fn delete_vertex_array_oes(&self, name: u32) {
+        if let Some(delete_vertex_arrays) = self.procs.delete_vertex_arrays {
+            delete_vertex_arrays(1, &name);
+        }
+    }
+
+    unsafe fn bind_vertex_array_oes(&self, name: u32) {
+        if let Some(bind_vertex_array) = self.procs.bind_vertex_array {
+            bind_vertex_array(name);
+        }
+    }
+
+    /// Load compressed image to 2D texture target
+    pub unsafe fn compressed_tex_image_2d(&self, target: u32, level: i32, format: u32, size: (usize, usize), data: &[u8]) -> Result<()> {
+        let compressed_tex_image_2d = self.procs.compressed_tex_image_2d
+            .ok_or_else(|| unsupported("compressed textures"))?;
+        compressed_tex_image_2d(target, level, format, size.0 as i32, size.1 as i32, 0,
+                                data.len() as i32, data.as_ptr() as *const c_void);
+        Ok(())
+    }
+
+    /// Load compressed sub image to 2D texture target
+    pub unsafe fn compressed_tex_sub_image_2d(&self, target: u32, level: i32, off: (usize, usize), size: (usize, usize), format: u32, data: &[u8]) -> Result<()> {
+        let compressed_tex_sub_image_2d = self.procs.compressed_tex_sub_image_2d
+            .ok_or_else(|| unsupported("compressed textures"))?;
+        compressed_tex_sub_image_2d(target, level, off.0 as i32, off.1 as i32, size.0 as i32, size.1 as i32,
+                                    format, data.len() as i32, data.as_ptr() as *const c_void);
+        Ok(())
+    }
+
+    /// Load compressed image to 3D texture target
+    pub unsafe fn compressed_tex_image_3d(&self, target: u32, level: i32, format: u32, size: (usize, usize, usize), data: &[u8]) -> Result<()> {
+        let compressed_tex_image_3d = self.procs.compressed_tex_image_3d
+            .ok_or_else(|| unsupported("compressed 3D textures"))?;
+        compressed_tex_image_3d(target, level, format, size.0 as i32, size.1 as i32, size.2 as i32, 0,
+                                data.len() as i32, data.as_ptr() as *const c_void);
+        Ok(())
+    }
+
+    /// Load compressed sub image to 3D texture target
+    pub unsafe fn compressed_tex_sub_image_3d(&self, target: u32, level: i32, off: (usize, usize, usize), size: (usize, usize, usize), format: u32, data: &[u8]) -> Result<()> {
+        let compressed_tex_sub_image_3d = self.procs.compressed_tex_sub_image_3d
+            .ok_or_else(|| unsupported("compressed 3D textures"))?;
+        compressed_tex_sub_image_3d(target, level, off.0 as i32, off.1 as i32, off.2 as i32,
+                                    size.0 as i32, size.1 as i32, size.2 as i32,
+                                    format, data.len() as i32, data.as_ptr() as *const c_void);
+        Ok(())
+    }
+}
+
+/// Vertex array object which is created by core functions or by `OES_vertex_array_object` extension
+pub(super) enum VertexArrayObject<G: HasContext> {
+    Core(G::VertexArray),
+    Oes(u32),
+}
+
+fn unsupported(feature: &str) -> Error {
+    Error::Unsupported(feature.into())
+}
+
+macro_rules! procs {
+    ($caps:ident; $($name:ident: fn($($arg:ty),*) = [$($symbol:literal if $cond:expr),+];)*) => {
+        /// Entry points which glow doesn't provide
+        #[derive(Default)]
+        struct Procs {
+            $($name: Option<unsafe extern "system" fn($($arg),*)>,)*
+        }
+
+        impl Procs {
+            /// Load entry points which is supported by context
+            fn load($caps: &Capabilities, loader: &mut dyn FnMut(&str) -> *const c_void) -> Self {
+                Self {
+                    $($name: None
+                      $(.or_else(|| if $cond { load(loader, $symbol) } else { None }))+
+                      .map(|proc| unsafe { transmute::<*const c_void, unsafe extern "system" fn($($arg),*)>(proc) }),)*
+                }
+            }
+        }
+    };
+}
+
+procs! {
+    caps;
+    get_program_iv: fn(u32, u32, *mut i32) = [
+        "glGetProgramiv" if true
+    ];
+    program_parameter_i32: fn(u32, u32, i32) = [
+        "glProgramParameteri" if has_core_program_binary(caps)
+    ];
+    get_program_binary: fn(u32, i32, *mut i32, *mut u32, *mut c_void) = [
+        "glGetProgramBinary" if has_core_program_binary(caps),
+        "glGetProgramBinaryOES" if caps.has_extension("OES_get_program_binary")
+    ];
+    program_binary: fn(u32, u32, *const c_void, i32) = [
+        "glProgramBinary" if has_core_program_binary(caps),
+        "glProgramBinaryOES" if caps.has_extension("OES_get_program_binary")
+    ];
+    gen_vertex_arrays: fn(i32, *mut u32) = [
+        "glGenVertexArraysOES" if caps.has_extension("OES_vertex_array_object")
+    ];
+    bind_vertex_array: fn(u32) = [
+        "glBindVertexArrayOES" if caps.has_extension("OES_vertex_array_object")
+    ];
+    delete_vertex_arrays: fn(i32, *const u32) = [
+        "glDeleteVertexArraysOES" if caps.has_extension("OES_vertex_array_object")
+    ];
+    draw_arrays_instanced: fn(u32, i32, i32, i32) = [
+        "glDrawArraysInstancedANGLE" if caps.has_extension("ANGLE_instanced_arrays"),
+        "glDrawArraysInstancedEXT" if caps.has_extension("EXT_instanced_arrays"),
+        "glDrawArraysInstancedARB" if caps.has_extension("ARB_draw_instanced")
+    ];
+    draw_elements_instanced: fn(u32, i32, u32, *const c_void, i32) = [
+        "glDrawElementsInstancedANGLE" if caps.has_extension("ANGLE_instanced_arrays"),
+        "glDrawElementsInstancedEXT" if caps.has_extension("EXT_instanced_arrays"),
+        "glDrawElementsInstancedARB" if caps.has_extension("ARB_draw_instanced")
+    ];
+    vertex_attrib_divisor: fn(u32, u32) = [
+        "glVertexAttribDivisorANGLE" if caps.has_extension("ANGLE_instanced_arrays"),
+        "glVertexAttribDivisorEXT" if caps.has_extension("EXT_instanced_arrays"),
+        "glVertexAttribDivisorARB" if caps.has_extension("ARB_instanced_arrays")
+    ];
+    draw_elements_base_vertex: fn(u32, i32, u32, *const c_void, i32) = [
+        "glDrawElementsBaseVertexOES" if caps.has_extension("OES_draw_elements_base_vertex"),
+        "glDrawElementsBaseVertexEXT" if caps.has_extension("EXT_draw_elements_base_vertex")
+    ];
+    draw_elements_instanced_base_vertex: fn(u32, i32, u32, *const c_void, i32, i32) = [
+        "glDrawElementsInstancedBaseVertexOES" if caps.has_extension("OES_draw_elements_base_vertex"),
+        "glDrawElementsInstancedBaseVertexEXT" if caps.has_extension("EXT_draw_elements_base_vertex")
+    ];
+    compressed_tex_image_2d: fn(u32, i32, u32, i32, i32, i32, i32, *const c_void) = [
+        "glCompressedTexImage2D" if caps.api != Api::WebGl
+    ];
+    compressed_tex_sub_image_2d: fn(u32, i32, i32, i32, i32, i32, u32, i32, *const c_void) = [
+        "glCompressedTexSubImage2D" if caps.api != Api::WebGl
+    ];
+    compressed_tex_image_3d: fn(u32, i32, u32, i32, i32, i32, i32, i32, *const c_void) = [
+        "glCompressedTexImage3D" if caps.api == Api::Gl || caps.is_version(Api::Gles, 3, 0)
+    ];
+    compressed_tex_sub_image_3d: fn(u32, i32, i32, i32, i32, i32, i32, i32, u32, i32, *const c_void) = [
+        "glCompressedTexSubImage3D" if caps.api == Api::Gl || caps.is_version(Api::Gles, 3, 0)
+    ];
+}
+
+fn has_core_program_binary(caps: &Capabilities) -> bool {
+    caps.is_version(Api::Gles, 3, 0) ||
+        caps.is_version(Api::Gl, 4, 1) ||
+        caps.has_extension("ARB_get_program_binary")
+}
+
+fn has_core_vertex_array(caps: &Capabilities) -> bool {
+    caps.is_version(Api::Gl, 3, 0) ||
+        caps.is_version(Api::Gles, 3, 0) ||
+        caps.is_version(Api::WebGl, 2, 0) ||
+        caps.has_extension("ARB_vertex_array_object")
+}
+
+fn has_core_instancing(caps: &Capabilities) -> bool {
+    caps.is_version(Api::Gl, 3, 3) ||
+        caps.is_version(Api::Gles, 3, 0) ||
+        caps.is_version(Api::WebGl, 2, 0)
+}
+
+fn has_core_base_vertex(caps: &Capabilities) -> bool {
+    caps.is_version(Api::Gl, 3, 2) ||
+        caps.is_version(Api::Gles, 3, 2)
+}
+
+fn load(loader: &mut dyn FnMut(&str) -> *const c_void, symbol: &str) -> Option<*const c_void> {
+    let proc = loader(symbol);
+    if proc.is_null() {
+        None
+    } else {
+        Some(proc)
+    }
+}
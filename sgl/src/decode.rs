@@ -0,0 +1,184 @@
+use super::{Result, Error, GL, HasContext, Texture, Texture2D, TextureFormat, Image, RGBA8888, RGB888, L8, LA88, Rgba8, Rgb8, La8};
+
+/// Options of image decoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DecodeOptions {
+    /// Multiply color channels by alpha
+    pub premultiply: bool,
+    /// Reverse order of rows
+    ///
+    /// Image files stores rows from top to bottom, so flipping puts the first row at the bottom like OpenGL expects.
+    pub flip: bool,
+}
+
+impl DecodeOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set premultiplication of alpha
+    pub fn with_premultiply(mut self, premultiply: bool) -> Self {
+        self.premultiply = premultiply;
+        self
+    }
+
+    /// Set vertical flipping
+    pub fn with_flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+}
+
+/// The trait for texture formats which images can be decoded to
+pub trait DecodeFormat: TextureFormat {
+    /// Convert decoded image to pixels of format
+    fn convert(image: ::image::DynamicImage, premultiply: bool) -> Image<Self::Pixel>;
+}
+
+fn premultiply(color: u8, alpha: u8) -> u8 {
+    ((color as u16 * alpha as u16 + 127) / 255) as u8
+}
+
+impl DecodeFormat for RGBA8888 {
+    fn convert(image: ::image::DynamicImage, premult: bool) -> Image<Rgba8> {
+        let image = image.to_rgba8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image.into_raw().chunks_exact(4)
+            .map(|pixel| if premult {
+                Rgba8::new(premultiply(pixel[0], pixel[3]),
+                           premultiply(pixel[1], pixel[3]),
+                           premultiply(pixel[2], pixel[3]),
+                           pixel[3])
+            } else {
+                Rgba8::new(pixel[0], pixel[1], pixel[2], pixel[3])
+            })
+            .collect();
+        Image::new(width, height, pixels)
+    }
+}
+
+impl DecodeFormat for RGB888 {
+    fn convert(image: ::image::DynamicImage, _premult: bool) -> Image<Rgb8> {
+        let image = image.to_rgb8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image.into_raw().chunks_exact(3)
+            .map(|pixel| Rgb8::new(pixel[0], pixel[1], pixel[2]))
+            .collect();
+        Image::new(width, height, pixels)
+    }
+}
+
+impl DecodeFormat for L8 {
+    fn convert(image: ::image::DynamicImage, _premult: bool) -> Image<u8> {
+        let image = image.to_luma8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        Image::new(width, height, image.into_raw())
+    }
+}
+
+impl DecodeFormat for LA88 {
+    fn convert(image: ::image::DynamicImage, premult: bool) -> Image<La8> {
+        let image = image.to_luma_alpha8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image.into_raw().chunks_exact(2)
+            .map(|pixel| if premult {
+                La8::new(premultiply(pixel[0], pixel[1]), pixel[1])
+            } else {
+                La8::new(pixel[0], pixel[1])
+            })
+            .collect();
+        Image::new(width, height, pixels)
+    }
+}
+
+/// Decode PNG or JPEG image to pixels of specified format
+pub fn decode_image<F: DecodeFormat>(data: &[u8], options: DecodeOptions) -> Result<Image<F::Pixel>> {
+    let image = ::image::load_from_memory(data)
+        .map_err(|error| Error::Decode(error.to_string()))?;
+    let mut image = F::convert(image, options.premultiply);
+    if options.flip {
+        image.flip_rows();
+    }
+    Ok(image)
+}
+
+impl<G: HasContext, F: DecodeFormat> Texture<G, Texture2D, F> {
+    /// Create texture from PNG or JPEG image
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn from_image_bytes(gl: &G, data: &[u8], options: DecodeOptions) -> Result<Self> where G: 'static {
+        let image = decode_image::<F>(data, options)?;
+        let texture = Self::new(gl)?;
+        // rows of decoded image is tightly packed
+        let alignment = unsafe { gl.get_parameter_i32(GL::UNPACK_ALIGNMENT) };
+        unsafe { gl.pixel_store_i32(GL::UNPACK_ALIGNMENT, 1); }
+        let result = texture.load(gl, image.size(), image.pixels());
+        unsafe { gl.pixel_store_i32(GL::UNPACK_ALIGNMENT, alignment); }
+        match result {
+            Ok(_) => Ok(texture),
+            Err(error) => {
+                texture.del(gl);
+                Err(error)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::image::{DynamicImage, RgbaImage, GrayAlphaImage};
+
+    #[test]
+    fn premultiply_rounding() {
+        assert_eq!(premultiply(255, 255), 255);
+        assert_eq!(premultiply(255, 0), 0);
+        assert_eq!(premultiply(0, 255), 0);
+        assert_eq!(premultiply(255, 128), 128);
+        assert_eq!(premultiply(128, 128), 64);
+        assert_eq!(premultiply(1, 127), 0);
+        assert_eq!(premultiply(1, 128), 1);
+        assert_eq!(premultiply(200, 100), 78);
+    }
+
+    #[test]
+    fn convert_rgba8888() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_raw(2, 1, vec![
+            255, 128, 0, 128,
+            10, 20, 30, 255,
+        ]).unwrap());
+
+        let straight = RGBA8888::convert(image.clone(), false);
+        assert_eq!(straight.size(), (2, 1));
+        assert_eq!(straight.pixels(), &[Rgba8::new(255, 128, 0, 128), Rgba8::new(10, 20, 30, 255)]);
+
+        let premultiplied = RGBA8888::convert(image, true);
+        assert_eq!(premultiplied.pixels(), &[Rgba8::new(128, 64, 0, 128), Rgba8::new(10, 20, 30, 255)]);
+    }
+
+    #[test]
+    fn convert_la88() {
+        let image = DynamicImage::ImageLumaA8(GrayAlphaImage::from_raw(1, 2, vec![
+            200, 100,
+            50, 0,
+        ]).unwrap());
+
+        let straight = LA88::convert(image.clone(), false);
+        assert_eq!(straight.size(), (1, 2));
+        assert_eq!(straight.pixels(), &[La8::new(200, 100), La8::new(50, 0)]);
+
+        let premultiplied = LA88::convert(image, true);
+        assert_eq!(premultiplied.pixels(), &[La8::new(78, 100), La8::new(0, 0)]);
+    }
+
+    #[test]
+    fn flip_rows() {
+        let mut image = Image::new(2, 3, vec![1u8, 2, 3, 4, 5, 6]);
+        image.flip_rows();
+        assert_eq!(image.pixels(), &[5, 6, 3, 4, 1, 2]);
+
+        let mut image = Image::new(3, 2, vec![1u8, 2, 3, 4, 5, 6]);
+        image.flip_rows();
+        assert_eq!(image.pixels(), &[4, 5, 6, 1, 2, 3]);
+    }
+}
@@ -1,26 +1,30 @@
-use super::{Result, HasContext, ShaderType, Shader, Program, Uniform, Attribs, Buffer, Array, Draw, DrawMode, Attrib};
+use super::{Result, HasContext, Capabilities, Preprocessor, ShaderType, Shader, Program, Uniform, Attribs, Buffer, Array, Draw, DrawMode, Attrib, Vector2};
 
 pub struct Demo<G: HasContext> {
     program: Program<G>,
     uniform: Uniform<G, (f32, f32)>,
-    geometry: (Buffer<G, Array<(f32, f32)>>, Attrib<G, (f32, f32)>),
+    geometry: (Buffer<G, Array<Vector2<f32>>>, Attrib<G, Vector2<f32>>),
     offset: (f32, f32),
 }
 
 impl<G: HasContext> Demo<G> {
-    pub fn new(gl: &G) -> Result<Self> {
-        let vertex_shader = Shader::new(gl, ShaderType::Vertex, include_str!("./demo.vert.glsl"))?;
-        let fragment_shader = Shader::new(gl, ShaderType::Fragment, include_str!("./demo.frag.glsl"))?;
+    pub fn new(gl: &G) -> Result<Self> where G: 'static {
+        let preprocessor = Preprocessor::new(Capabilities::query(gl).dialect())
+            .with_file("demo.vert.glsl", include_str!("./demo.vert.glsl"))
+            .with_file("demo.frag.glsl", include_str!("./demo.frag.glsl"));
+
+        let vertex_shader = Shader::new(gl, ShaderType::Vertex, preprocessor.process(ShaderType::Vertex, "demo.vert.glsl")?)?;
+        let fragment_shader = Shader::new(gl, ShaderType::Fragment, preprocessor.process(ShaderType::Fragment, "demo.frag.glsl")?)?;
         let program = Program::new(gl, vec![vertex_shader, fragment_shader])?;
 
-        let attrib = program.attrib(gl, "position");
-        let uniform = program.uniform(gl, "offset");
+        let attrib = program.attrib(gl, "position")?;
+        let uniform = program.uniform(gl, "offset")?;
 
         let mut vertex = attrib.buffer(gl)?;
         vertex.load(gl, &[
-            (0.0, 0.5),
-            (-0.5, -0.5),
-            (0.5, -0.5),
+            Vector2::new(0.0, 0.5),
+            Vector2::new(-0.5, -0.5),
+            Vector2::new(0.5, -0.5),
         ]);
         let offset = (0.0, 0.0);
 
@@ -1,7 +1,3 @@
-//#ifdef GL_ES
-//#version 100
-//#endif
-
 attribute vec2 position;
 uniform vec2 offset;
 
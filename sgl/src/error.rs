@@ -0,0 +1,331 @@
+use core::fmt;
+use super::{ShaderType, GlslType};
+
+/// Kind of OpenGL object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Object {
+    Shader,
+    Program,
+    Buffer,
+    Texture,
+    VertexArray,
+    Framebuffer,
+    Sampler,
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Object::*;
+        f.write_str(match self {
+            Shader => "shader",
+            Program => "program",
+            Buffer => "buffer",
+            Texture => "texture",
+            VertexArray => "vertex array",
+            Framebuffer => "framebuffer",
+            Sampler => "sampler",
+        })
+    }
+}
+
+/// Severity of diagnostic message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Severity::*;
+        f.write_str(match self {
+            Error => "error",
+            Warning => "warning",
+            Info => "info",
+        })
+    }
+}
+
+/// Single message from shader compiler or program linker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Message severity
+    pub severity: Severity,
+    /// Index of source string
+    pub source: Option<u32>,
+    /// Line number in source string (starting from 1)
+    pub line: Option<u32>,
+    /// Column number in source line (when driver reports it)
+    pub column: Option<u32>,
+    /// Message text
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Parse driver info log into diagnostics
+    ///
+    /// Understands the common formats of Mesa (`0:12(5): error: ...`),
+    /// NVIDIA (`0(12) : error C0000: ...`) and ANGLE/Adreno/Mali/Apple (`ERROR: 0:12: ...`) drivers.
+    /// Lines in unknown format are kept as is without location.
+    pub fn parse_log(log: &str) -> Vec<Self> {
+        log.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(Self::parse_line)
+            .collect()
+    }
+
+    fn parse_line(line: &str) -> Self {
+        let (severity, rest) = match split_severity(line) {
+            Some((severity, rest)) => (Some(severity), rest),
+            None => (None, line),
+        };
+
+        let (location, rest) = match split_location(rest) {
+            Some((location, rest)) => (Some(location), rest),
+            None => (None, rest),
+        };
+
+        let (severity, message) = match severity {
+            Some(severity) => (severity, rest),
+            None => match split_severity(rest) {
+                Some((severity, rest)) => (severity, rest),
+                None => (if location.is_some() { Severity::Error } else { Severity::Info }, rest),
+            },
+        };
+
+        let (source, line, column) = location
+            .map(|(source, line, column)| (Some(source), Some(line), column))
+            .unwrap_or((None, None, None));
+
+        Self { severity, source, line, column, message: message.into() }
+    }
+
+    /// Get the source line which diagnostic refers to
+    pub fn source_line<'a>(&self, source: &'a str) -> Option<&'a str> {
+        let line = self.line?;
+        if line == 0 {
+            return None;
+        }
+        source.lines().nth(line as usize - 1)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let (Some(source), Some(line)) = (self.source, self.line) {
+            write!(f, "{}:{}", source, line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+            f.write_str(": ")?;
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Split severity prefix like `ERROR:`, `warning:` or `error C1008:`
+fn split_severity(line: &str) -> Option<(Severity, &str)> {
+    let lower = line.to_ascii_lowercase();
+    let (severity, len) = if lower.starts_with("error") {
+        (Severity::Error, "error".len())
+    } else if lower.starts_with("warning") {
+        (Severity::Warning, "warning".len())
+    } else if lower.starts_with("info") {
+        (Severity::Info, "info".len())
+    } else {
+        return None;
+    };
+    let rest = &line[len..];
+    if !rest.starts_with(|c: char| c == ':' || c.is_whitespace()) {
+        return None;
+    }
+    // skip optional message code up to colon
+    let colon = rest.find(':')?;
+    if rest[..colon].trim().contains(char::is_whitespace) {
+        return None;
+    }
+    Some((severity, rest[colon + 1..].trim_start()))
+}
+
+/// Split location prefix like `0:12:`, `0:12(5):` or `0(12) :`
+fn split_location(line: &str) -> Option<((u32, u32, Option<u32>), &str)> {
+    let (source, rest) = split_number(line)?;
+    let (line, column, rest) = if let Some(rest) = rest.strip_prefix(':') {
+        let (line, rest) = split_number(rest)?;
+        match rest.strip_prefix('(') {
+            Some(rest) => {
+                let (column, rest) = split_number(rest)?;
+                (line, Some(column), rest.strip_prefix(')')?)
+            },
+            None => (line, None, rest),
+        }
+    } else if let Some(rest) = rest.strip_prefix('(') {
+        let (line, rest) = split_number(rest)?;
+        (line, None, rest.strip_prefix(')')?)
+    } else {
+        return None;
+    };
+    let rest = rest.trim_start().strip_prefix(':')?;
+    Some(((source, line, column), rest.trim_start()))
+}
+
+fn split_number(line: &str) -> Option<(u32, &str)> {
+    let end = line.find(|c: char| !c.is_ascii_digit()).unwrap_or(line.len());
+    Some((line[..end].parse().ok()?, &line[end..]))
+}
+
+/// Error type
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// Unable to create OpenGL object
+    Create(Object, String),
+    /// Shader compilation failed
+    Compile(ShaderType, Vec<Diagnostic>),
+    /// Program linking failed
+    Link(Vec<Diagnostic>),
+    /// Program has no active uniform with such name
+    NoUniform(String),
+    /// Program has no active attribute with such name
+    NoAttrib(String),
+    /// Rust data type doesn't match GLSL declaration
+    TypeMismatch {
+        name: String,
+        rust: GlslType,
+        glsl: GlslType,
+    },
+    /// Unable to resolve included file
+    Include {
+        name: String,
+        from: Option<(String, u32)>,
+    },
+    /// Feature isn't supported by context
+    Unsupported(String),
+    /// Unable to read file
+    Read {
+        path: String,
+        reason: String,
+    },
+    /// Draw call can't be done with current data
+    Draw(String),
+    /// Unable to decode image
+    Decode(String),
+    /// Object has no data loaded yet
+    Empty(Object),
+}
+
+impl Error {
+    /// Get compiler or linker diagnostics
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        use self::Error::*;
+        match self {
+            Compile(_, diagnostics) | Link(diagnostics) => diagnostics,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Error::*;
+        match self {
+            Create(object, reason) => write!(f, "Unable to create {}: {}", object, reason),
+            Compile(typ, diagnostics) => {
+                write!(f, "Unable to compile {} shader", typ)?;
+                write_diagnostics(f, diagnostics)
+            },
+            Link(diagnostics) => {
+                f.write_str("Unable to link program")?;
+                write_diagnostics(f, diagnostics)
+            },
+            NoUniform(name) => write!(f, "No active uniform `{}` found", name),
+            NoAttrib(name) => write!(f, "No active attribute `{}` found", name),
+            TypeMismatch { name, rust, glsl } => write!(f, "Type mismatch for `{}`: data is {} but declared as {}", name, rust, glsl),
+            Include { name, from } => {
+                write!(f, "Unable to include `{}`", name)?;
+                if let Some((file, line)) = from {
+                    write!(f, " from {}:{}", file, line)?;
+                }
+                Ok(())
+            },
+            Unsupported(feature) => write!(f, "Not supported by context: {}", feature),
+            Read { path, reason } => write!(f, "Unable to read `{}`: {}", path, reason),
+            Draw(reason) => write!(f, "Invalid draw: {}", reason),
+            Decode(reason) => write!(f, "Unable to decode image: {}", reason),
+            Empty(object) => write!(f, "No data is loaded to {}", object),
+        }
+    }
+}
+
+fn write_diagnostics(f: &mut fmt::Formatter, diagnostics: &[Diagnostic]) -> fmt::Result {
+    for diagnostic in diagnostics {
+        write!(f, "\n{}", diagnostic)?;
+    }
+    Ok(())
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diag(severity: Severity, location: Option<(u32, u32, Option<u32>)>, message: &str) -> Diagnostic {
+        let (source, line, column) = location
+            .map(|(source, line, column)| (Some(source), Some(line), column))
+            .unwrap_or((None, None, None));
+        Diagnostic { severity, source, line, column, message: message.into() }
+    }
+
+    #[test]
+    fn parse_log_lines() {
+        use self::Severity::*;
+
+        let table = [
+            // Mesa
+            ("0:1(10): error: syntax error, unexpected NEW_IDENTIFIER",
+             diag(Error, Some((0, 1, Some(10))), "syntax error, unexpected NEW_IDENTIFIER")),
+            ("0:5(12): warning: `color' used uninitialized",
+             diag(Warning, Some((0, 5, Some(12))), "`color' used uninitialized")),
+            ("error: vertex shader output `v_uv' not written",
+             diag(Error, None, "vertex shader output `v_uv' not written")),
+            // NVIDIA
+            ("0(12) : error C0000: syntax error, unexpected identifier, expecting \"::\" at token \"foo\"",
+             diag(Error, Some((0, 12, None)), "syntax error, unexpected identifier, expecting \"::\" at token \"foo\"")),
+            ("0(3) : warning C7533: global variable gl_FragColor is deprecated after version 120",
+             diag(Warning, Some((0, 3, None)), "global variable gl_FragColor is deprecated after version 120")),
+            // ANGLE
+            ("ERROR: 0:3: 'vec5' : no matching overloaded function found",
+             diag(Error, Some((0, 3, None)), "'vec5' : no matching overloaded function found")),
+            ("WARNING: 0:2: 'GL_OES_standard_derivatives' : extension is not supported",
+             diag(Warning, Some((0, 2, None)), "'GL_OES_standard_derivatives' : extension is not supported")),
+            // Unknown format
+            ("Vertex info",
+             diag(Info, None, "Vertex info")),
+        ];
+
+        for (line, expected) in &table {
+            assert_eq!(Diagnostic::parse_log(line), std::slice::from_ref(expected), "{}", line);
+        }
+    }
+
+    #[test]
+    fn parse_log_multiline() {
+        let log = "ERROR: 0:3: 'x' : undeclared identifier\n\nERROR: 1 compilation errors.  No code generated.\n\n";
+        let diagnostics = Diagnostic::parse_log(log);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(diagnostics[1].line, None);
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].message, "1 compilation errors.  No code generated.");
+    }
+
+    #[test]
+    fn diagnostic_display() {
+        let diagnostic = diag(Severity::Error, Some((1, 12, Some(5))), "`foo' undeclared");
+        assert_eq!(diagnostic.to_string(), "1:12:5: error: `foo' undeclared");
+        assert_eq!(diagnostic.source_line("a\n".repeat(12).as_str()), Some("a"));
+    }
+}
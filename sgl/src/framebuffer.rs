@@ -0,0 +1,108 @@
+use super::{Result, Error, Object, GL, HasContext, Resource, DeletionQueue, Texture, Texture2D, TextureFormat, Image, as_bytes_mut, track::Track};
+
+/// Framebuffer object
+///
+/// Allows to render to textures and to read them back.
+pub struct Framebuffer<G: HasContext> {
+    framebuffer: G::Framebuffer,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
+}
+
+impl<G: HasContext> Framebuffer<G> {
+    /// Create framebuffer
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G) -> Result<Self> where G: 'static {
+        let framebuffer = unsafe { gl.create_framebuffer() }
+            .map_err(|error| Error::Create(Object::Framebuffer, error))?;
+        Ok(Self { framebuffer, queue: DeletionQueue::current(), track: Track::new(Object::Framebuffer) })
+    }
+
+    /// Attach framebuffer to deletion queue
+    ///
+    /// Attached framebuffer is scheduled for deletion when dropped.
+    /// New framebuffer is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
+    }
+
+    /// Delete framebuffer
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
+        unsafe { gl.delete_framebuffer(self.framebuffer); }
+    }
+
+    /// Attach level of texture as color buffer or as depth and stencil buffer for depth stencil format
+    ///
+    /// Fails when texture format isn't renderable.
+    pub fn attach_texture<F: TextureFormat>(&self, gl: &G, texture: &Texture<G, Texture2D, F>, level: i32) -> Result<()> {
+        let attachment = if F::FORMAT == GL::DEPTH_STENCIL {
+            GL::DEPTH_STENCIL_ATTACHMENT
+        } else {
+            GL::COLOR_ATTACHMENT0
+        };
+        self.bind(gl);
+        let status = unsafe {
+            gl.framebuffer_texture_2d(GL::FRAMEBUFFER, attachment, GL::TEXTURE_2D, Some(texture.texture), level);
+            gl.check_framebuffer_status(GL::FRAMEBUFFER)
+        };
+        self.unbind(gl);
+
+        if status != GL::FRAMEBUFFER_COMPLETE {
+            return Err(Error::Unsupported(format!("framebuffer with texture (status 0x{:04x})", status)));
+        }
+        Ok(())
+    }
+
+    /// Bind framebuffer so drawing goes to it
+    pub fn bind(&self, gl: &G) {
+        unsafe { gl.bind_framebuffer(GL::FRAMEBUFFER, Some(self.framebuffer)); }
+    }
+
+    /// Bind default framebuffer back
+    pub fn unbind(&self, gl: &G) {
+        unsafe { gl.bind_framebuffer(GL::FRAMEBUFFER, None); }
+    }
+
+    /// Read region of color buffer
+    ///
+    /// NOTE: Only `RGBA8888` format is guaranteed to be readable on OpenGL ES and WebGL.
+    pub fn read_pixels<F: TextureFormat>(&self, gl: &G, x: usize, y: usize, width: usize, height: usize) -> Image<F::Pixel> {
+        self.bind(gl);
+        let image = read_pixels::<G, F>(gl, x, y, width, height);
+        self.unbind(gl);
+        image
+    }
+
+    /// Read region of default framebuffer
+    ///
+    /// NOTE: Only `RGBA8888` format is guaranteed to be readable on OpenGL ES and WebGL.
+    pub fn read_default_pixels<F: TextureFormat>(gl: &G, x: usize, y: usize, width: usize, height: usize) -> Image<F::Pixel> {
+        read_pixels::<G, F>(gl, x, y, width, height)
+    }
+}
+
+impl<G: HasContext> Drop for Framebuffer<G> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Framebuffer(self.framebuffer));
+            self.track.release();
+        }
+    }
+}
+
+/// Read region of bound framebuffer
+fn read_pixels<G: HasContext, F: TextureFormat>(gl: &G, x: usize, y: usize, width: usize, height: usize) -> Image<F::Pixel> {
+    let mut image = Image::zeroed(width, height);
+    unsafe {
+        // rows of pixels is tightly packed
+        let alignment = gl.get_parameter_i32(GL::PACK_ALIGNMENT);
+        gl.pixel_store_i32(GL::PACK_ALIGNMENT, 1);
+        gl.read_pixels(x as i32, y as i32, width as i32, height as i32, F::FORMAT, F::TYPE,
+                       as_bytes_mut(image.pixels_mut()));
+        gl.pixel_store_i32(GL::PACK_ALIGNMENT, alignment);
+    }
+    image
+}
@@ -0,0 +1,82 @@
+use super::{Pod, as_bytes, zeroed_vec};
+
+/// Image of typed pixels
+///
+/// Rows is stored from bottom to top like OpenGL does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image<P> {
+    width: usize,
+    height: usize,
+    pixels: Vec<P>,
+}
+
+impl<P: Pod> Image<P> {
+    /// Create image from pixels
+    pub fn new(width: usize, height: usize, pixels: Vec<P>) -> Self {
+        if width * height != pixels.len() {
+            panic!("Image size {}x{} doesn't match the number of pixels {}", width, height, pixels.len());
+        }
+        Self { width, height, pixels }
+    }
+
+    /// Create image with zeroed pixels
+    pub fn zeroed(width: usize, height: usize) -> Self {
+        Self { width, height, pixels: zeroed_vec(width * height) }
+    }
+
+    /// Image width in pixels
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Image height in pixels
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Image size in pixels
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Get pixel at specified position
+    pub fn pixel(&self, x: usize, y: usize) -> Option<&P> {
+        if x < self.width && y < self.height {
+            self.pixels.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Pixels of image
+    pub fn pixels(&self) -> &[P] {
+        &self.pixels
+    }
+
+    /// Mutable pixels of image
+    pub fn pixels_mut(&mut self) -> &mut [P] {
+        &mut self.pixels
+    }
+
+    /// Get pixels of image
+    pub fn into_pixels(self) -> Vec<P> {
+        self.pixels
+    }
+
+    /// Raw data of image
+    pub fn as_bytes(&self) -> &[u8] {
+        as_bytes(&self.pixels)
+    }
+
+    /// Reverse order of rows
+    ///
+    /// Useful to convert image to top to bottom order which is usual for image files.
+    pub fn flip_rows(&mut self) {
+        let width = self.width;
+        let height = self.height;
+        for row in 0..height / 2 {
+            let (top, bottom) = self.pixels.split_at_mut((height - row - 1) * width);
+            top[row * width..][..width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
+}
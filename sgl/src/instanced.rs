@@ -0,0 +1,53 @@
+use super::{HasContext, Geometry, VertexStream, DrawMode};
+#[cfg(feature = "validate")]
+use super::{Result, validate};
+
+/// Geometry with per-instance attributes
+///
+/// The first field is per-vertex geometry and the second is stream of per-instance attributes,
+/// like `Instanced((vertices, attribs), (instances, instance_attribs))`.
+///
+/// Plain drawing draws instance for each element of per-instance stream,
+/// [`Draw::draw_instanced`](super::Draw::draw_instanced) allows to specify number of instances.
+/// Requires OpenGL ES 3.0, OpenGL 3.3 or `ANGLE_instanced_arrays` extension.
+pub struct Instanced<D, S>(pub D, pub S);
+
+impl<G: HasContext, D: Geometry<G>, S: VertexStream<G>> Geometry<G> for Instanced<D, S> {
+    fn bind(&self, gl: &G) {
+        self.0.bind(gl);
+        self.1.enable_stream(gl);
+        self.1.set_stream_divisor(gl, 1);
+    }
+    fn unbind(&self, gl: &G) {
+        self.1.set_stream_divisor(gl, 0);
+        self.1.disable_stream(gl);
+        self.0.unbind(gl);
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        self.0.draw_bound_instanced(gl, mode, offset, count, self.1.stream_length());
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        self.0.draw_bound_instanced(gl, mode, offset, count, instances);
+    }
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        self.0.draw_bound_instanced_base_vertex(gl, mode, offset, count, self.1.stream_length(), base_vertex);
+    }
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        self.0.draw_bound_instanced_base_vertex(gl, mode, offset, count, instances, base_vertex);
+    }
+    fn length(&self) -> i32 {
+        self.0.length()
+    }
+    fn is_instanced(&self) -> bool {
+        true
+    }
+    fn is_indexed(&self) -> bool {
+        self.0.is_indexed()
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32, restart: bool) -> Result<()> {
+        self.0.validate(offset, count, None, base_vertex, restart)?;
+        let length = self.1.stream_vertices()?;
+        validate::check_instances(instances.unwrap_or(length), length)
+    }
+}
@@ -0,0 +1,493 @@
+use std::{borrow::Cow, convert::TryFrom};
+use super::{Result, Error, GL, HasContext, Texture, Texture2D, TextureTarget, TextureFormat, texture::image_length};
+
+const KTX1_IDENTIFIER: [u8; 12] = [0xAB, b'K', b'T', b'X', b' ', b'1', b'1', 0xBB, b'\r', b'\n', 0x1A, b'\n'];
+const KTX2_IDENTIFIER: [u8; 12] = [0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n'];
+
+/// Texture image with mipmap levels from KTX or KTX2 container
+///
+/// Only two-dimensional textures without array layers and cube faces is supported.
+#[derive(Debug, Clone)]
+pub struct Ktx<'a> {
+    format: u32,
+    data_type: u32,
+    internal: u32,
+    size: (usize, usize),
+    alignment: usize,
+    levels: Vec<&'a [u8]>,
+}
+
+impl<'a> Ktx<'a> {
+    /// Parse KTX or KTX2 container
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        if data.starts_with(&KTX1_IDENTIFIER) {
+            Self::parse_ktx1(data)
+        } else if data.starts_with(&KTX2_IDENTIFIER) {
+            Self::parse_ktx2(data)
+        } else {
+            Err(error("not a KTX container"))
+        }
+    }
+
+    /// OpenGL format of pixels or internal format when compressed
+    pub fn format(&self) -> u32 {
+        self.format
+    }
+
+    /// OpenGL type of pixels or zero when compressed
+    pub fn data_type(&self) -> u32 {
+        self.data_type
+    }
+
+    /// OpenGL sized internal format
+    pub fn internal(&self) -> u32 {
+        self.internal
+    }
+
+    /// Size of the first level
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Data of mipmap levels starting from the largest one
+    pub fn levels(&self) -> &[&'a [u8]] {
+        &self.levels
+    }
+
+    /// Check that container has data of specified format
+    pub fn is_format<F: TextureFormat>(&self) -> bool {
+        self.format == F::FORMAT && self.data_type == F::TYPE &&
+            self.internal == sized_internal(F::INTERNAL, F::TYPE)
+    }
+
+    fn parse_ktx1(data: &'a [u8]) -> Result<Self> {
+        let big_endian = match read_u32(data, 12)? {
+            0x04030201 => false,
+            0x01020304 => true,
+            _ => return Err(error("invalid endianness")),
+        };
+        let field = |offset| read_u32(data, offset).map(|value| if big_endian { value.swap_bytes() } else { value });
+
+        let data_type = field(16)?;
+        let type_size = field(20)?;
+        let format = field(24)?;
+        let internal_format = field(28)?;
+        let size = (field(36)? as usize, field(40)? as usize);
+        check_layout(size, field(44)?, field(48)?, field(52)?)?;
+        if big_endian && type_size > 1 {
+            return Err(error("big endian data"));
+        }
+
+        let (format, data_type) = if format == 0 {
+            (internal_format, 0)
+        } else {
+            (format, data_type)
+        };
+        let internal = sized_internal(internal_format, data_type);
+
+        let mut offset = (field(60)? as usize).checked_add(64)
+            .ok_or_else(|| error("truncated level data"))?;
+        let mut levels = Vec::new();
+        for _ in 0..field(56)?.max(1) {
+            let length = field(offset)? as usize;
+            offset += 4;
+            levels.push(level_data(data, offset, length)?);
+            // levels is aligned to 4 bytes
+            offset += (length + 3) & !3;
+        }
+
+        Ok(Self { format, data_type, internal, size, alignment: 4, levels })
+    }
+
+    fn parse_ktx2(data: &'a [u8]) -> Result<Self> {
+        let vk_format = read_u32(data, 12)?;
+        let size = (read_u32(data, 20)? as usize, read_u32(data, 24)? as usize);
+        check_layout(size, read_u32(data, 28)?, read_u32(data, 32)?, read_u32(data, 36)?)?;
+        if read_u32(data, 44)? != 0 {
+            return Err(error("supercompressed data"));
+        }
+
+        let (format, data_type, internal) = vk_format_to_gl(vk_format)
+            .ok_or_else(|| error(format!("unsupported Vulkan format {}", vk_format)))?;
+
+        let levels = (0..read_u32(data, 40)?.max(1) as usize)
+            .map(|level| {
+                let offset = usize::try_from(read_u64(data, 80 + level * 24)?)
+                    .map_err(|_| error("truncated level data"))?;
+                let length = usize::try_from(read_u64(data, 88 + level * 24)?)
+                    .map_err(|_| error("truncated level data"))?;
+                level_data(data, offset, length)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { format, data_type, internal, size, alignment: 1, levels })
+    }
+}
+
+impl<G: HasContext, F: TextureFormat> Texture<G, Texture2D, F> {
+    /// Create texture from KTX or KTX2 container
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn from_ktx(gl: &G, data: &[u8]) -> Result<Self> where G: 'static {
+        let ktx = Ktx::parse(data)?;
+        let texture = Self::new(gl)?;
+        match texture.load_ktx(gl, &ktx) {
+            Ok(_) => Ok(texture),
+            Err(error) => {
+                texture.del(gl);
+                Err(error)
+            },
+        }
+    }
+
+    /// Load all mipmap levels from KTX or KTX2 container
+    ///
+    /// Fails when format of container doesn't match texture format.
+    pub fn load_ktx(&self, gl: &G, ktx: &Ktx) -> Result<()> {
+        if !ktx.is_format::<F>() {
+            return Err(error(format!("format 0x{:04x} doesn't match texture format 0x{:04x}", ktx.internal, F::INTERNAL)));
+        }
+
+        let levels = ktx.levels.iter().enumerate()
+            .map(|(level, data)| {
+                let (width, height) = Texture2D::level_size(ktx.size, level);
+                unpack_rows::<F>(data, width, height, ktx.alignment)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let alignment = unsafe { gl.get_parameter_i32(GL::UNPACK_ALIGNMENT) };
+        unsafe { gl.pixel_store_i32(GL::UNPACK_ALIGNMENT, 1); }
+        let result = self.load_raw_levels(gl, ktx.size, levels.iter().map(|data| data.as_ref()));
+        unsafe { gl.pixel_store_i32(GL::UNPACK_ALIGNMENT, alignment); }
+
+        result
+    }
+}
+
+/// Strip padding of rows
+fn unpack_rows<F: TextureFormat>(data: &[u8], width: usize, height: usize, alignment: usize) -> Result<Cow<'_, [u8]>> {
+    let length = image_length::<F>(width, height, 1);
+    if F::COMPRESSED || alignment == 1 {
+        return if data.len() == length {
+            Ok(Cow::Borrowed(data))
+        } else {
+            Err(error("level data size mismatch"))
+        };
+    }
+    let row = length / height;
+    let stride = row.div_ceil(alignment) * alignment;
+    if data.len() != stride * height {
+        Err(error("level data size mismatch"))
+    } else if stride == row {
+        Ok(Cow::Borrowed(data))
+    } else {
+        Ok(Cow::Owned(data.chunks(stride).flat_map(|line| &line[..row]).copied().collect()))
+    }
+}
+
+fn check_layout(size: (usize, usize), depth: u32, layers: u32, faces: u32) -> Result<()> {
+    if size.0 == 0 || size.1 == 0 || depth > 1 || layers > 0 || faces > 1 {
+        Err(error("only 2D textures is supported"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Sized internal format which corresponds to unsized one
+///
+/// Containers stores sized formats even for unsized formats of OpenGL ES 2.0.
+fn sized_internal(internal: u32, data_type: u32) -> u32 {
+    match (internal, data_type) {
+        (GL::RGB, GL::UNSIGNED_BYTE) => GL::RGB8,
+        (GL::RGBA, GL::UNSIGNED_BYTE) => GL::RGBA8,
+        (GL::RGB, GL::UNSIGNED_SHORT_5_6_5) => GL::RGB565,
+        (GL::RGBA, GL::UNSIGNED_SHORT_4_4_4_4) => GL::RGBA4,
+        (GL::RGBA, GL::UNSIGNED_SHORT_5_5_5_1) => GL::RGB5_A1,
+        _ => internal,
+    }
+}
+
+/// Map Vulkan format to OpenGL format, type and sized internal format
+fn vk_format_to_gl(format: u32) -> Option<(u32, u32, u32)> {
+    Some(match format {
+        2 => (GL::RGBA, GL::UNSIGNED_SHORT_4_4_4_4, GL::RGBA4),
+        4 => (GL::RGB, GL::UNSIGNED_SHORT_5_6_5, GL::RGB565),
+        6 => (GL::RGBA, GL::UNSIGNED_SHORT_5_5_5_1, GL::RGB5_A1),
+        9 => (GL::RED, GL::UNSIGNED_BYTE, GL::R8),
+        16 => (GL::RG, GL::UNSIGNED_BYTE, GL::RG8),
+        23 => (GL::RGB, GL::UNSIGNED_BYTE, GL::RGB8),
+        37 => (GL::RGBA, GL::UNSIGNED_BYTE, GL::RGBA8),
+        43 => (GL::RGBA, GL::UNSIGNED_BYTE, GL::SRGB8_ALPHA8),
+        97 => (GL::RGBA, GL::HALF_FLOAT, GL::RGBA16F),
+        109 => (GL::RGBA, GL::FLOAT, GL::RGBA32F),
+        _ => {
+            let format = vk_compressed_format_to_gl(format)?;
+            (format, 0, format)
+        },
+    })
+}
+
+/// Map Vulkan compressed format to OpenGL format
+fn vk_compressed_format_to_gl(format: u32) -> Option<u32> {
+    Some(match format {
+        147 => GL::COMPRESSED_RGB8_ETC2,
+        148 => GL::COMPRESSED_SRGB8_ETC2,
+        149 => GL::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+        150 => GL::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+        151 => GL::COMPRESSED_RGBA8_ETC2_EAC,
+        152 => GL::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC,
+        153 => GL::COMPRESSED_R11_EAC,
+        154 => GL::COMPRESSED_SIGNED_R11_EAC,
+        155 => GL::COMPRESSED_RG11_EAC,
+        156 => GL::COMPRESSED_SIGNED_RG11_EAC,
+        // ASTC formats goes in pairs of linear and sRGB variants
+        157..=184 => {
+            let index = format - 157;
+            (if index & 1 == 0 { 0x93B0 } else { 0x93D0 }) + index / 2
+        },
+        _ => return None,
+    })
+}
+
+fn level_data(data: &[u8], offset: usize, length: usize) -> Result<&[u8]> {
+    offset.checked_add(length)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| error("truncated level data"))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    offset.checked_add(4)
+        .and_then(|end| data.get(offset..end))
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| error("truncated data"))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(read_u32(data, offset)? as u64 | (read_u32(data, offset + 4)? as u64) << 32)
+}
+
+fn error(reason: impl Into<String>) -> Error {
+    Error::Decode(format!("KTX: {}", reason.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RGB888, RGB565, RGBA8888, SRGB8_ALPHA8, ETC2_RGB8, ASTC_4x4, ASTC_SRGB_4x4, ASTC_12x12, ASTC_SRGB_12x12};
+
+    /// Build KTX container with header fields in specified byte order
+    fn ktx1(big_endian: bool, header: [u32; 6], size: (u32, u32), levels: &[&[u8]]) -> Vec<u8> {
+        let [data_type, type_size, format, internal_format, base_format, key_value_length] = header;
+        let word = |value: u32| if big_endian { value.to_be_bytes() } else { value.to_le_bytes() };
+
+        let mut data = KTX1_IDENTIFIER.to_vec();
+        for value in &[0x04030201, data_type, type_size, format, internal_format, base_format,
+                       size.0, size.1, 0, 0, 1, levels.len() as u32, key_value_length] {
+            data.extend_from_slice(&word(*value));
+        }
+        data.resize(data.len() + key_value_length as usize, 0);
+        for level in levels {
+            data.extend_from_slice(&word(level.len() as u32));
+            data.extend_from_slice(level);
+            data.resize((data.len() + 3) & !3, 0);
+        }
+        data
+    }
+
+    /// Build KTX2 container without data format descriptor
+    fn ktx2(vk_format: u32, size: (u32, u32), supercompression: u32, levels: &[&[u8]]) -> Vec<u8> {
+        let mut data = KTX2_IDENTIFIER.to_vec();
+        for value in &[vk_format, 1, size.0, size.1, 0, 0, 1, levels.len() as u32, supercompression, 0, 0, 0, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0; 16]);
+
+        let mut offset = 80 + 24 * levels.len();
+        for level in levels {
+            for value in &[offset as u64, level.len() as u64, level.len() as u64] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            offset += level.len();
+        }
+        for level in levels {
+            data.extend_from_slice(level);
+        }
+        data
+    }
+
+    const RGB: [u32; 6] = [GL::UNSIGNED_BYTE, 1, GL::RGB, GL::RGB8, GL::RGB, 0];
+
+    #[test]
+    fn ktx1_little_endian() {
+        let level0 = [1u8; 24];
+        let level1 = [2u8; 4];
+        let data = ktx1(false, [GL::UNSIGNED_BYTE, 1, GL::RGBA, GL::RGBA8, GL::RGBA, 12], (3, 2), &[&level0, &level1]);
+        let ktx = Ktx::parse(&data).unwrap();
+
+        assert!(ktx.is_format::<RGBA8888>());
+        assert!(!ktx.is_format::<RGB888>());
+        assert!(!ktx.is_format::<SRGB8_ALPHA8>());
+        assert_eq!(ktx.internal(), GL::RGBA8);
+        assert_eq!(ktx.size(), (3, 2));
+        assert_eq!(ktx.alignment, 4);
+        assert_eq!(ktx.levels(), &[&level0[..], &level1[..]]);
+    }
+
+    #[test]
+    fn ktx1_big_endian() {
+        let level = [7u8; 24];
+        let data = ktx1(true, RGB, (3, 2), &[&level]);
+        let ktx = Ktx::parse(&data).unwrap();
+
+        assert!(ktx.is_format::<RGB888>());
+        assert_eq!(ktx.size(), (3, 2));
+        assert_eq!(ktx.levels(), &[&level[..]]);
+
+        // multi-byte pixels would need swapping
+        let data = ktx1(true, [GL::UNSIGNED_SHORT_5_6_5, 2, GL::RGB, GL::RGB565, GL::RGB, 0], (2, 2), &[&[0; 8]]);
+        assert!(Ktx::parse(&data).is_err());
+
+        let mut data = ktx1(false, RGB, (3, 2), &[&level]);
+        data[12..16].copy_from_slice(&[1, 1, 1, 1]);
+        assert!(Ktx::parse(&data).is_err());
+    }
+
+    #[test]
+    fn ktx1_internal_format() {
+        let level = [0u8; 16];
+
+        // sRGB shares format and type with linear one
+        let data = ktx1(false, [GL::UNSIGNED_BYTE, 1, GL::RGBA, GL::SRGB8_ALPHA8, GL::RGBA, 0], (2, 2), &[&level]);
+        let ktx = Ktx::parse(&data).unwrap();
+        assert!(ktx.is_format::<SRGB8_ALPHA8>());
+        assert!(!ktx.is_format::<RGBA8888>());
+
+        // unsized internal format is treated as sized one
+        let data = ktx1(false, [GL::UNSIGNED_BYTE, 1, GL::RGBA, GL::RGBA, GL::RGBA, 0], (2, 2), &[&level]);
+        let ktx = Ktx::parse(&data).unwrap();
+        assert_eq!(ktx.internal(), GL::RGBA8);
+        assert!(ktx.is_format::<RGBA8888>());
+
+        let data = ktx1(false, [GL::UNSIGNED_SHORT_5_6_5, 2, GL::RGB, GL::RGB565, GL::RGB, 0], (2, 2), &[&level[..8]]);
+        assert!(Ktx::parse(&data).unwrap().is_format::<RGB565>());
+    }
+
+    #[test]
+    fn ktx1_compressed() {
+        let level = [0u8; 8];
+        let data = ktx1(false, [0, 1, 0, GL::COMPRESSED_RGB8_ETC2, GL::RGB, 0], (4, 4), &[&level]);
+        let ktx = Ktx::parse(&data).unwrap();
+
+        assert_eq!(ktx.format(), GL::COMPRESSED_RGB8_ETC2);
+        assert_eq!(ktx.internal(), GL::COMPRESSED_RGB8_ETC2);
+        assert_eq!(ktx.data_type(), 0);
+        assert!(ktx.is_format::<ETC2_RGB8>());
+    }
+
+    #[test]
+    fn ktx1_truncated() {
+        let data = ktx1(false, RGB, (3, 2), &[&[0; 24]]);
+
+        match Ktx::parse(&data[..data.len() - 1]) {
+            Err(Error::Decode(reason)) => assert_eq!(reason, "KTX: truncated level data"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(Ktx::parse(&data[..60]).is_err());
+        assert!(Ktx::parse(&data[..12]).is_err());
+        assert!(Ktx::parse(b"not a ktx file").is_err());
+    }
+
+    #[test]
+    fn ktx_level_overflow() {
+        let mut data = ktx1(false, RGB, (3, 2), &[&[0; 24]]);
+        // key-value data length
+        data[60..64].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Ktx::parse(&data).is_err());
+
+        let data = ktx2(158, (4, 4), 0, &[&[0; 16]]);
+        for (offset, length) in &[(u64::MAX, 16), (80, u64::MAX), (u64::MAX - 8, 16)] {
+            let mut data = data.clone();
+            data[80..88].copy_from_slice(&offset.to_le_bytes());
+            data[88..96].copy_from_slice(&length.to_le_bytes());
+            match Ktx::parse(&data) {
+                Err(Error::Decode(reason)) => assert_eq!(reason, "KTX: truncated level data"),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn ktx1_layout() {
+        let mut data = ktx1(false, RGB, (3, 2), &[&[0; 24]]);
+        // six cube faces
+        data[52..56].copy_from_slice(&6u32.to_le_bytes());
+        assert!(Ktx::parse(&data).is_err());
+    }
+
+    #[test]
+    fn ktx2_levels() {
+        let level0 = [3u8; 64];
+        let level1 = [4u8; 16];
+        let data = ktx2(158, (8, 8), 0, &[&level0, &level1]);
+        let ktx = Ktx::parse(&data).unwrap();
+
+        assert!(ktx.is_format::<ASTC_SRGB_4x4>());
+        assert_eq!(ktx.size(), (8, 8));
+        assert_eq!(ktx.alignment, 1);
+        assert_eq!(ktx.levels(), &[&level0[..], &level1[..]]);
+
+        assert!(Ktx::parse(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn ktx2_srgb() {
+        let data = ktx2(43, (2, 2), 0, &[&[0; 16]]);
+        let ktx = Ktx::parse(&data).unwrap();
+
+        assert!(ktx.is_format::<SRGB8_ALPHA8>());
+        assert!(!ktx.is_format::<RGBA8888>());
+        assert!(Ktx::parse(&ktx2(37, (2, 2), 0, &[&[0; 16]])).unwrap().is_format::<RGBA8888>());
+    }
+
+    #[test]
+    fn ktx2_unsupported() {
+        assert!(Ktx::parse(&ktx2(158, (8, 8), 1, &[&[0; 64]])).is_err());
+        assert!(Ktx::parse(&ktx2(1000, (8, 8), 0, &[&[0; 64]])).is_err());
+    }
+
+    #[test]
+    fn unpack_rows_padding() {
+        // rows of 9 bytes padded to 12
+        let data = (0..24).collect::<Vec<u8>>();
+        let rows = unpack_rows::<RGB888>(&data, 3, 2, 4).unwrap();
+        assert!(matches!(rows, Cow::Owned(_)));
+        assert_eq!(&rows[..], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+
+        // rows is already aligned
+        let data = [0u8; 16];
+        assert!(matches!(unpack_rows::<RGB565>(&data, 2, 4, 4).unwrap(), Cow::Borrowed(_)));
+        assert!(matches!(unpack_rows::<RGB888>(&data[..12], 2, 2, 1).unwrap(), Cow::Borrowed(_)));
+
+        assert!(unpack_rows::<RGB888>(&data, 3, 2, 4).is_err());
+        assert!(unpack_rows::<ETC2_RGB8>(&data, 4, 4, 4).is_err());
+    }
+
+    #[test]
+    fn vk_formats() {
+        assert_eq!(vk_format_to_gl(37), Some((GL::RGBA, GL::UNSIGNED_BYTE, GL::RGBA8)));
+        assert_eq!(vk_format_to_gl(43), Some((GL::RGBA, GL::UNSIGNED_BYTE, GL::SRGB8_ALPHA8)));
+        assert_eq!(vk_format_to_gl(147), Some((GL::COMPRESSED_RGB8_ETC2, 0, GL::COMPRESSED_RGB8_ETC2)));
+
+        assert_eq!(vk_compressed_format_to_gl(157), Some(ASTC_4x4::FORMAT));
+        assert_eq!(vk_compressed_format_to_gl(158), Some(ASTC_SRGB_4x4::FORMAT));
+        assert_eq!(vk_compressed_format_to_gl(183), Some(ASTC_12x12::FORMAT));
+        assert_eq!(vk_compressed_format_to_gl(184), Some(ASTC_SRGB_12x12::FORMAT));
+        for format in 157..=184 {
+            let gl_format = vk_compressed_format_to_gl(format).unwrap();
+            assert!((0x93B0..=0x93BD).contains(&gl_format) || (0x93D0..=0x93DD).contains(&gl_format));
+            assert_eq!(vk_format_to_gl(format), Some((gl_format, 0, gl_format)));
+        }
+
+        assert_eq!(vk_compressed_format_to_gl(156), Some(GL::COMPRESSED_SIGNED_RG11_EAC));
+        assert_eq!(vk_format_to_gl(185), None);
+    }
+}
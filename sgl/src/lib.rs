@@ -1,19 +1,79 @@
+mod error;
+mod pod;
+mod vector;
+mod pixel;
+mod reflect;
+mod caps;
+mod context;
 mod attrib;
+mod vertex;
 mod uniform;
 mod buffer;
+mod stream;
+mod vertex_array;
+mod streams;
+mod instanced;
+mod restart;
+#[cfg(feature = "validate")]
+mod validate;
 mod texture;
+mod compressed;
+mod ktx;
+mod sampler;
+mod image;
+mod framebuffer;
+#[cfg(feature = "image")]
+mod decode;
 mod program;
+mod preprocess;
+mod translate;
+mod track;
+mod queue;
+#[cfg(not(target_arch = "wasm32"))]
+mod cache;
+mod relocate;
+#[cfg(feature = "hot-reload")]
+mod reload;
 pub mod demo;
 
 pub use glow::{self as GL, HasContext, Context};
 
+pub use self::error::*;
+pub use self::pod::*;
+pub use self::vector::*;
+pub use self::pixel::*;
+pub use self::reflect::*;
+pub use self::caps::*;
+pub use self::context::{make_current, release_current};
 pub use self::attrib::*;
+pub use self::vertex::*;
 pub use self::uniform::*;
 pub use self::buffer::*;
+pub use self::stream::*;
+pub use self::vertex_array::*;
+pub use self::streams::*;
+pub use self::instanced::*;
+pub use self::restart::*;
 pub use self::texture::*;
+pub use self::compressed::*;
+pub use self::ktx::*;
+pub use self::sampler::*;
+pub use self::image::*;
+pub use self::framebuffer::*;
+#[cfg(feature = "image")]
+pub use self::decode::*;
 pub use self::program::*;
+pub use self::preprocess::*;
+#[cfg(feature = "debug-resources")]
+pub use self::track::*;
+pub use self::queue::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::cache::*;
+pub use self::relocate::*;
+#[cfg(feature = "hot-reload")]
+pub use self::reload::*;
 
-pub type Result<T> = std::result::Result<T, String>;
+pub type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(feature = "glam")]
 pub use glam::{Vec2, Vec3, Vec4, Mat2, Mat3, Mat4};
@@ -23,3 +83,6 @@ pub use stretch::geometry::{Point, Size, Rect};
 
 #[cfg(feature = "colours")]
 pub use colours::{Rgb, Rgba, Hsv, Hsva, Hsl, Hsla};
+
+#[cfg(feature = "derive")]
+pub use sgl_derive::{Vertex, Uniforms, Pod};
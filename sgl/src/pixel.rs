@@ -0,0 +1,67 @@
+use super::{Pod, Half};
+
+macro_rules! pixel_types {
+    ($($(#[$meta: meta])* $name: ident: $type: ty, $($field: ident),+;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq)]
+            #[repr(C)]
+            pub struct $name {
+                $(pub $field: $type,)+
+            }
+
+            impl $name {
+                pub const fn new($($field: $type),+) -> Self {
+                    Self { $($field),+ }
+                }
+            }
+
+            unsafe impl Pod for $name {}
+        )*
+    };
+}
+
+pixel_types! {
+    /// Pixel with 8-bit red, green and blue channels
+    #[derive(Eq, Hash)]
+    Rgb8: u8, r, g, b;
+    /// Pixel with 8-bit red, green, blue and alpha channels
+    #[derive(Eq, Hash)]
+    Rgba8: u8, r, g, b, a;
+    /// Pixel with 8-bit luminance and alpha channels
+    #[derive(Eq, Hash)]
+    La8: u8, l, a;
+    /// Pixel with 8-bit red and green channels
+    #[derive(Eq, Hash)]
+    Rg8: u8, r, g;
+    /// Pixel with half float red, green, blue and alpha channels
+    #[derive(Eq, Hash)]
+    Rgba16F: Half, r, g, b, a;
+    /// Pixel with float red, green, blue and alpha channels
+    Rgba32F: f32, r, g, b, a;
+}
+
+/// Pixel with 24-bit depth and 8-bit stencil
+///
+/// Depth is stored in upper bits like `UNSIGNED_INT_24_8` expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Depth24Stencil8(pub u32);
+
+unsafe impl Pod for Depth24Stencil8 {}
+
+impl Depth24Stencil8 {
+    pub const fn new(depth: u32, stencil: u8) -> Self {
+        Self(depth << 8 | stencil as u32)
+    }
+
+    /// Depth value in range `0..=0xffffff`
+    pub const fn depth(&self) -> u32 {
+        self.0 >> 8
+    }
+
+    /// Stencil value
+    pub const fn stencil(&self) -> u8 {
+        self.0 as u8
+    }
+}
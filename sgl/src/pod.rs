@@ -0,0 +1,42 @@
+use core::{
+    mem::{size_of_val, zeroed},
+    slice::{from_raw_parts, from_raw_parts_mut},
+};
+
+/// The trait for plain data which can be viewed as bytes
+///
+/// Buffer elements and texture pixels should implement this trait.
+/// Usually it implemented using `#[derive(Pod)]` on `#[repr(C)]` struct.
+///
+/// # Safety
+///
+/// Type must have defined layout (`#[repr(C)]` or `#[repr(transparent)]`),
+/// no padding bytes, no pointers or references and any bit pattern must be a valid value.
+/// Rust tuples isn't suitable because its layout isn't guaranteed.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! pod_impls {
+    ($($type: ty),*) => {
+        $(unsafe impl Pod for $type {})*
+    };
+}
+
+pod_impls!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// View slice of plain data as bytes
+pub fn as_bytes<T: Pod>(data: &[T]) -> &[u8] {
+    unsafe { from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) }
+}
+
+/// View mutable slice of plain data as bytes
+pub fn as_bytes_mut<T: Pod>(data: &mut [T]) -> &mut [u8] {
+    unsafe { from_raw_parts_mut(data.as_mut_ptr() as *mut u8, size_of_val(data)) }
+}
+
+/// Create vector of zeroed plain data
+pub fn zeroed_vec<T: Pod>(length: usize) -> Vec<T> {
+    // any bit pattern is valid for plain data
+    vec![unsafe { zeroed() }; length]
+}
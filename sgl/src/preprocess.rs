@@ -0,0 +1,245 @@
+use core::fmt::{Display, Write};
+use super::{Result, Error, Api, ShaderType};
+
+/// GLSL dialect of target context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// OpenGL ES 2.0 (GLSL ES 1.00)
+    Gles2,
+    /// OpenGL ES 3.x (GLSL ES 3.00)
+    Gles3,
+    /// Desktop OpenGL 3.3+ core profile (GLSL 3.30)
+    GlCore,
+    /// Desktop OpenGL 2.0 - 3.2 with GLSL version number (110, 120, 130, 140 or 150)
+    Gl(u16),
+    /// WebGL 1.0 (GLSL ES 1.00)
+    WebGl,
+    /// WebGL 2.0 (GLSL ES 3.00)
+    WebGl2,
+}
+
+impl Dialect {
+    /// Get dialect which is suitable for API version
+    pub fn new(api: Api, version: (u8, u8)) -> Self {
+        use self::Dialect::*;
+        match api {
+            Api::Gl => match version {
+                (0..=1, _) | (2, 0) => Gl(110),
+                (2, _) => Gl(120),
+                (3, 0) => Gl(130),
+                (3, 1) => Gl(140),
+                (3, 2) => Gl(150),
+                _ => GlCore,
+            },
+            Api::Gles => if version.0 >= 3 { Gles3 } else { Gles2 },
+            Api::WebGl => if version.0 >= 2 { WebGl2 } else { WebGl },
+        }
+    }
+
+    /// Language version for `#version` directive
+    pub fn version(&self) -> &'static str {
+        use self::Dialect::*;
+        match self {
+            Gles2 | WebGl => "100",
+            Gles3 | WebGl2 => "300 es",
+            GlCore => "330 core",
+            Gl(110) => "110",
+            Gl(120) => "120",
+            Gl(130) => "130",
+            Gl(140) => "140",
+            Gl(_) => "150",
+        }
+    }
+
+    /// Dialect is one of GLSL ES
+    pub fn is_es(&self) -> bool {
+        !matches!(self, Dialect::GlCore | Dialect::Gl(_))
+    }
+
+    /// Write `#line` directive which sets number of next line
+    ///
+    /// GLSL ES 1.00 and GLSL before 3.30 treats number as the number of directive line itself,
+    /// newer versions treats it as the number of next line.
+    fn write_line<W: Write>(&self, out: &mut W, line: u32, source: usize) {
+        use self::Dialect::*;
+        let line = match self {
+            Gles2 | WebGl | Gl(_) => line - 1,
+            _ => line,
+        };
+        let _ = writeln!(out, "#line {} {}", line, source);
+    }
+
+    /// Write `#version` directive of source and default precision for shader
+    fn write_header<W: Write>(&self, out: &mut W, typ: ShaderType, version: &str, extensions: &[String], defines: &[(String, String)]) {
+        use self::Dialect::*;
+        let _ = writeln!(out, "#version {}", version);
+        for extension in extensions {
+            let _ = writeln!(out, "{}", extension);
+        }
+        for (name, value) in defines {
+            let _ = writeln!(out, "#define {} {}", name, value);
+        }
+        if typ == ShaderType::Fragment {
+            let _ = match self {
+                Gles2 | WebGl => writeln!(out, "#ifdef GL_FRAGMENT_PRECISION_HIGH\nprecision highp float;\n#else\nprecision mediump float;\n#endif"),
+                Gles3 | WebGl2 => writeln!(out, "precision highp float;"),
+                GlCore | Gl(_) => Ok(()),
+            };
+        }
+    }
+}
+
+/// Preprocessed shader source
+#[derive(Debug, Clone)]
+pub struct Source {
+    /// Resulting source text
+    pub text: String,
+    /// Names of files by source string number
+    pub files: Vec<String>,
+}
+
+impl Source {
+    /// Get name of file by source string number which reported in diagnostics
+    pub fn file(&self, source: u32) -> Option<&str> {
+        self.files.get(source as usize).map(String::as_str)
+    }
+}
+
+impl AsRef<str> for Source {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+/// GLSL shader preprocessor
+///
+/// Resolves `#include` directives using virtual set of files,
+/// injects `#define`s and prepends `#version` and precision header for target dialect.
+/// Sources is written in language specified by `#version` of main file (`100` by default)
+/// and translated to target dialect (see [`Dialect::translate`]).
+///
+/// Source strings in generated `#line` directives corresponds to files in order of inclusion,
+/// so diagnostics can be mapped back to files using [`Source::file`].
+#[derive(Debug, Clone)]
+pub struct Preprocessor {
+    dialect: Dialect,
+    files: Vec<(String, String)>,
+    defines: Vec<(String, String)>,
+}
+
+impl Preprocessor {
+    /// Create preprocessor for target dialect
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect, files: Vec::new(), defines: Vec::new() }
+    }
+
+    /// Add file to virtual file set
+    pub fn with_file<N: Into<String>, S: Into<String>>(mut self, name: N, source: S) -> Self {
+        self.files.push((name.into(), source.into()));
+        self
+    }
+
+    /// Add definition
+    pub fn with_define<N: Into<String>, V: Display>(mut self, name: N, value: V) -> Self {
+        self.defines.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Target dialect
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Virtual file set has file with specified name
+    pub fn has_file(&self, name: &str) -> bool {
+        self.files.iter().any(|(file, _)| file == name)
+    }
+
+    /// Preprocess shader from file with specified name
+    ///
+    /// Each file is included only once, so cyclic and repeated includes are ignored.
+    pub fn process(&self, typ: ShaderType, name: &str) -> Result<Source> {
+        let mut state = State { files: Vec::new(), version: None, extensions: Vec::new(), body: String::new() };
+
+        self.include(&mut state, name, None)?;
+
+        let mut text = String::new();
+        let version = state.version.as_deref().unwrap_or("100");
+        self.dialect.write_header(&mut text, typ, version, &state.extensions, &self.defines);
+        text.push_str(&state.body);
+
+        let text = self.dialect.translate(typ, &text).into_owned();
+
+        Ok(Source { text, files: state.files })
+    }
+
+    fn include(&self, state: &mut State, name: &str, from: Option<(usize, u32)>) -> Result<()> {
+        let source = self.files.iter()
+            .find(|(file, _)| file == name)
+            .map(|(_, source)| source)
+            .ok_or_else(|| Error::Include {
+                name: name.into(),
+                from: from.map(|(index, line)| (state.files[index].clone(), line)),
+            })?;
+
+        if state.files.iter().any(|file| file == name) {
+            return Ok(());
+        }
+
+        let index = state.files.len();
+        state.files.push(name.into());
+        self.dialect.write_line(&mut state.body, 1, index);
+
+        for (number, line) in source.lines().enumerate() {
+            let number = number as u32 + 1;
+            match directive(line) {
+                Some(("version", args)) => {
+                    if index == 0 {
+                        state.version = Some(args.trim().into());
+                    }
+                    state.body.push('\n');
+                },
+                Some(("extension", _)) => {
+                    state.extensions.push(line.trim().into());
+                    state.body.push('\n');
+                },
+                Some(("include", args)) => {
+                    let file = include_name(args)
+                        .ok_or_else(|| Error::Include {
+                            name: args.trim().into(),
+                            from: Some((name.into(), number)),
+                        })?;
+                    self.include(state, file, Some((index, number)))?;
+                    self.dialect.write_line(&mut state.body, number + 1, index);
+                },
+                _ => {
+                    state.body.push_str(line);
+                    state.body.push('\n');
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct State {
+    files: Vec<String>,
+    version: Option<String>,
+    extensions: Vec<String>,
+    body: String,
+}
+
+/// Get file name from arguments of `#include` directive
+pub(super) fn include_name(args: &str) -> Option<&str> {
+    let args = args.trim();
+    args.strip_prefix('"').and_then(|args| args.strip_suffix('"'))
+        .or_else(|| args.strip_prefix('<').and_then(|args| args.strip_suffix('>')))
+}
+
+/// Split preprocessor directive to name and arguments
+pub(super) fn directive(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start().strip_prefix('#')?.trim_start();
+    let end = line.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(line.len());
+    Some((&line[..end], &line[end..]))
+}
@@ -1,61 +1,133 @@
-use super::{Result, GL, HasContext, AsAttrib, Attrib, AsUniform, Uniform};
+use core::fmt;
+use super::{Result, Error, Object, Diagnostic, Dialect, Resource, DeletionQueue, track::Track, GL, HasContext, AsAttrib, Attrib, AsUniform, Uniform, UniformInfo, AttribInfo, GlslType, Vertex, VertexAttribs, reflect::base_name};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u32)]
 pub enum ShaderType {
     Vertex = GL::VERTEX_SHADER,
     Fragment = GL::FRAGMENT_SHADER,
 }
 
+impl fmt::Display for ShaderType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::ShaderType::*;
+        f.write_str(match self {
+            Vertex => "vertex",
+            Fragment => "fragment",
+        })
+    }
+}
+
 /// GLSL Shader
 pub struct Shader<G: HasContext> {
     pub(super) shader: G::Shader,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
 }
 
 impl<G: HasContext> Shader<G> {
     /// Create shader of specified type using specified source
-    pub fn new<S: AsRef<str>>(gl: &G, typ: ShaderType, src: S) -> Result<Self> {
+    ///
+    /// Source is translated to dialect of current context (see [`Dialect::translate`]).
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new<S: AsRef<str>>(gl: &G, typ: ShaderType, src: S) -> Result<Self> where G: 'static {
+        let src = Dialect::detect(gl).translate(typ, src.as_ref());
         unsafe {
-            let shader = gl.create_shader(typ as u32)?;
-            gl.shader_source(shader, src.as_ref());
+            let shader = gl.create_shader(typ as u32)
+                .map_err(|error| Error::Create(Object::Shader, error))?;
+            gl.shader_source(shader, &src);
             gl.compile_shader(shader);
             if gl.get_shader_compile_status(shader) {
-                Ok(Self { shader })
+                Ok(Self { shader, queue: DeletionQueue::current(), track: Track::new(Object::Shader) })
             } else {
-                let error = gl.get_shader_info_log(shader);
+                let log = gl.get_shader_info_log(shader);
                 gl.delete_shader(shader);
-                Err(error)
+                Err(Error::Compile(typ, Diagnostic::parse_log(&log)))
+            }
+        }
+    }
+
+    /// Create shaders using specified sources
+    ///
+    /// When some shader fails to compile already compiled shaders is deleted.
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new_all<S: AsRef<str>>(gl: &G, sources: &[(ShaderType, S)]) -> Result<Vec<Self>> where G: 'static {
+        let mut shaders = Vec::with_capacity(sources.len());
+        for (typ, src) in sources {
+            match Self::new(gl, *typ, src) {
+                Ok(shader) => shaders.push(shader),
+                Err(error) => {
+                    for shader in shaders {
+                        shader.del(gl);
+                    }
+                    return Err(error);
+                },
             }
         }
+        Ok(shaders)
+    }
+
+    /// Attach shader to deletion queue
+    ///
+    /// Attached shader is scheduled for deletion when dropped.
+    /// New shader is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
     }
 
     /// Delete shader
     ///
-    /// NOTE: We cannot simply use `Drop` trait because we need context for deleting shader object
+    /// NOTE: We cannot simply use `Drop` trait because we need context for deleting shader object,
+    /// use [`Shader::attach`] to defer deletion instead
     /// NOTE2: Usually we dont need to do it manually
-    pub fn del(self, gl: &G) {
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
         unsafe { gl.delete_shader(self.shader); }
     }
 }
 
+impl<G: HasContext> Drop for Shader<G> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Shader(self.shader));
+            self.track.release();
+        }
+    }
+}
+
 /// GLSL Program
 pub struct Program<G: HasContext> {
     pub(super) program: G::Program,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
 }
 
 impl<G: HasContext> Program<G> {
     /// Create program using specified shaders
-    pub fn new(gl: &G, shaders: Vec<Shader<G>>) -> Result<Self> {
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G, shaders: Vec<Shader<G>>) -> Result<Self> where G: 'static {
+        Self::link(gl, shaders, |_| Ok(()))
+    }
+
+    /// Create program setting parameters before linking
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub(super) fn link<F: FnOnce(G::Program) -> Result<()>>(gl: &G, shaders: Vec<Shader<G>>, setup: F) -> Result<Self> where G: 'static {
         unsafe {
-            let program = gl.create_program()?;
+            let program = gl.create_program()
+                .map_err(|error| Error::Create(Object::Program, error))?;
             for shader in &shaders {
                 gl.attach_shader(program, shader.shader);
             }
-            gl.link_program(program);
-            let res = if gl.get_program_link_status(program) {
-                Ok(Self { program })
-            } else {
-                Err(gl.get_program_info_log(program))
-            };
+            let res = setup(program).and_then(|_| {
+                gl.link_program(program);
+                if gl.get_program_link_status(program) {
+                    Ok(Self::from_raw(program))
+                } else {
+                    Err(Error::Link(Diagnostic::parse_log(&gl.get_program_info_log(program))))
+                }
+            });
             for shader in shaders.into_iter() {
                 gl.detach_shader(program, shader.shader);
                 shader.del(gl);
@@ -67,10 +139,27 @@ impl<G: HasContext> Program<G> {
         }
     }
 
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub(super) fn from_raw(program: G::Program) -> Self where G: 'static {
+        Self { program, queue: DeletionQueue::current(), track: Track::new(Object::Program) }
+    }
+
+    /// Attach program to deletion queue
+    ///
+    /// Attached program is scheduled for deletion when dropped.
+    /// New program is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
+    }
+
     /// Delete program
     ///
-    /// NOTE: we cannot simply use `Drop` trait because we need context for deleting program object
-    pub fn del(self, gl: &G) {
+    /// NOTE: we cannot simply use `Drop` trait because we need context for deleting program object,
+    /// use [`Program::attach`] to defer deletion instead
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
         unsafe { gl.delete_program(self.program); }
     }
 
@@ -84,25 +173,93 @@ impl<G: HasContext> Program<G> {
         unsafe { gl.use_program(None); }
     }
 
-    pub fn uniform<T: AsUniform<G>, S: AsRef<str>>(&self, gl: &G, name: S) -> Uniform<G, T> {
+    /// Get active uniforms
+    pub fn active_uniforms(&self, gl: &G) -> Vec<UniformInfo> {
+        unsafe {
+            (0..gl.get_active_uniforms(self.program))
+                .filter_map(|index| gl.get_active_uniform(self.program, index))
+                .map(|GL::ActiveUniform { name, utype, size }| UniformInfo {
+                    name, typ: GlslType::from_gl(utype), size,
+                })
+                .collect()
+        }
+    }
+
+    /// Get active attributes
+    pub fn active_attribs(&self, gl: &G) -> Vec<AttribInfo> {
+        unsafe {
+            (0..gl.get_active_attributes(self.program))
+                .filter_map(|index| gl.get_active_attribute(self.program, index))
+                .map(|GL::ActiveAttribute { name, atype, size }| AttribInfo {
+                    name, typ: GlslType::from_gl(atype), size,
+                })
+                .collect()
+        }
+    }
+
+    /// Find uniform location by name
+    ///
+    /// Fails when program has no active uniform with such name
+    /// or when uniform declared with type which doesn't match `T`.
+    pub fn uniform<T: AsUniform<G>, S: AsRef<str>>(&self, gl: &G, name: S) -> Result<Uniform<G, T>> {
         let name = name.as_ref();
-        let location = unsafe { gl.get_uniform_location(self.program, name) };
+        let info = self.active_uniforms(gl).into_iter()
+            .find(|info| base_name(&info.name) == base_name(name))
+            .ok_or_else(|| Error::NoUniform(name.into()))?;
 
-        if location.is_none() {
-            eprintln!("No uniform `{}` found", name);
+        if !T::TYPE.loads_uniform(info.typ) {
+            return Err(Error::TypeMismatch { name: name.into(), rust: T::TYPE, glsl: info.typ });
         }
 
-        Uniform::new(location)
+        let location = unsafe { gl.get_uniform_location(self.program, name) }
+            .ok_or_else(|| Error::NoUniform(name.into()))?;
+
+        Ok(Uniform::new(name, Some(location)))
     }
 
-    pub fn attrib<T: AsAttrib<G>, S: AsRef<str>>(&self, gl: &G, name: S) -> Attrib<G, T> {
+    /// Find attribute location by name
+    ///
+    /// Fails when program has no active attribute with such name
+    /// or when attribute declared with type which doesn't match `T`.
+    pub fn attrib<T: AsAttrib<G>, S: AsRef<str>>(&self, gl: &G, name: S) -> Result<Attrib<G, T>> {
         let name = name.as_ref();
-        let location = unsafe { gl.get_attrib_location(self.program, name) };
+        let location = self.attrib_location(gl, &self.active_attribs(gl), name, T::TYPE)?;
 
-        if location.is_none() {
-            eprintln!("No attribute `{}` found", name)
+        Ok(Attrib::new(name, Some(location)))
+    }
+
+    /// Bind fields of vertex struct to attributes
+    ///
+    /// Fails when some field has no matching active attribute.
+    pub fn vertex<V: Vertex<G>>(&self, gl: &G) -> Result<VertexAttribs<G, V>> {
+        let active = self.active_attribs(gl);
+
+        let attribs = V::fields().into_iter()
+            .map(|field| Ok((self.attrib_location(gl, &active, field.name, field.typ)?, field)))
+            .collect::<Result<_>>()?;
+
+        Ok(VertexAttribs::new(attribs))
+    }
+
+    pub(super) fn attrib_location(&self, gl: &G, active: &[AttribInfo], name: &str, typ: GlslType) -> Result<u32> {
+        let info = active.iter()
+            .find(|info| base_name(&info.name) == base_name(name))
+            .ok_or_else(|| Error::NoAttrib(name.into()))?;
+
+        if !typ.feeds_attrib(info.typ) {
+            return Err(Error::TypeMismatch { name: name.into(), rust: typ, glsl: info.typ });
         }
 
-        Attrib::new(location)
+        unsafe { gl.get_attrib_location(self.program, name) }
+            .ok_or_else(|| Error::NoAttrib(name.into()))
+    }
+}
+
+impl<G: HasContext> Drop for Program<G> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Program(self.program));
+            self.track.release();
+        }
     }
 }
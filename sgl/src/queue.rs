@@ -0,0 +1,118 @@
+use std::{
+    any::Any,
+    rc::Rc,
+    cell::RefCell,
+};
+use super::{HasContext, context::Current};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Box<dyn Any>>> = const { RefCell::new(None) };
+}
+
+/// GL object which is waiting for deletion
+pub enum Resource<G: HasContext> {
+    Buffer(G::Buffer),
+    Texture(G::Texture),
+    Shader(G::Shader),
+    Program(G::Program),
+    VertexArray(G::VertexArray),
+    /// Vertex array object of `OES_vertex_array_object` extension
+    VertexArrayOes(u32),
+    Framebuffer(G::Framebuffer),
+    Sampler(G::Sampler),
+}
+
+impl<G: HasContext> Resource<G> {
+    /// Delete object
+    pub fn del(self, gl: &G) {
+        use self::Resource::*;
+        unsafe {
+            match self {
+                Buffer(buffer) => gl.delete_buffer(buffer),
+                Texture(texture) => gl.delete_texture(texture),
+                Shader(shader) => gl.delete_shader(shader),
+                Program(program) => gl.delete_program(program),
+                VertexArray(vertex_array) => gl.delete_vertex_array(vertex_array),
+                VertexArrayOes(name) => Current::get(gl).delete_vertex_array_oes(name),
+                Framebuffer(framebuffer) => gl.delete_framebuffer(framebuffer),
+                Sampler(sampler) => gl.delete_sampler(sampler),
+            }
+        }
+    }
+}
+
+/// Deferred deletion queue
+///
+/// Handles which is attached to queue pushes own objects onto it when dropped,
+/// so objects can be deleted later when context is available.
+/// Handles is attached to current queue of thread on creation (see [`DeletionQueue::make_current`]).
+/// The queue is cheaply clonable and clones shares the same objects.
+pub struct DeletionQueue<G: HasContext> {
+    objects: Rc<RefCell<Vec<Resource<G>>>>,
+}
+
+impl<G: HasContext> Clone for DeletionQueue<G> {
+    fn clone(&self) -> Self {
+        Self { objects: self.objects.clone() }
+    }
+}
+
+impl<G: HasContext> Default for DeletionQueue<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: HasContext> DeletionQueue<G> {
+    /// Create empty queue
+    pub fn new() -> Self {
+        Self { objects: Rc::new(RefCell::new(Vec::new())) }
+    }
+
+    /// Schedule object for deletion
+    pub fn push(&self, object: Resource<G>) {
+        self.objects.borrow_mut().push(object);
+    }
+
+    /// Number of objects waiting for deletion
+    pub fn len(&self) -> usize {
+        self.objects.borrow().len()
+    }
+
+    /// No objects waiting for deletion
+    pub fn is_empty(&self) -> bool {
+        self.objects.borrow().is_empty()
+    }
+
+    /// Delete all scheduled objects
+    ///
+    /// Should be called at safe point when context is current, like at start of frame.
+    pub fn flush(&self, gl: &G) {
+        let objects = self.objects.replace(Vec::new());
+        for object in objects {
+            object.del(gl);
+        }
+    }
+}
+
+impl<G: HasContext + 'static> DeletionQueue<G> {
+    /// Make queue current for this thread
+    ///
+    /// Objects which is created while queue is current is attached to it.
+    /// Current queue is forgotten by [`release_current`](super::release_current).
+    pub fn make_current(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(Box::new(self.clone())));
+    }
+
+    /// Get current queue of this thread
+    pub fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().as_ref()
+                     .and_then(|queue| queue.downcast_ref::<Self>())
+                     .cloned())
+    }
+}
+
+/// Forget current queue of this thread
+pub(super) fn release_current() {
+    CURRENT.with(|current| *current.borrow_mut() = None);
+}
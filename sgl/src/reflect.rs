@@ -0,0 +1,177 @@
+use core::fmt;
+use super::GL;
+
+macro_rules! glsl_types {
+    ($($name: ident, $gl_type: ident, $glsl: literal;)*) => {
+        /// GLSL data type of uniform or attribute
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum GlslType {
+            $($name,)*
+            /// Type which is unknown for us
+            Other(u32),
+        }
+
+        impl GlslType {
+            /// Convert from OpenGL type constant
+            pub fn from_gl(value: u32) -> Self {
+                match value {
+                    $(GL::$gl_type => GlslType::$name,)*
+                    _ => GlslType::Other(value),
+                }
+            }
+
+            /// Convert to OpenGL type constant
+            pub fn to_gl(&self) -> u32 {
+                match self {
+                    $(GlslType::$name => GL::$gl_type,)*
+                    GlslType::Other(value) => *value,
+                }
+            }
+        }
+
+        impl fmt::Display for GlslType {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(GlslType::$name => f.write_str($glsl),)*
+                    GlslType::Other(value) => write!(f, "<type 0x{:04x}>", value),
+                }
+            }
+        }
+    };
+}
+
+glsl_types! {
+    Float, FLOAT, "float";
+    Vec2, FLOAT_VEC2, "vec2";
+    Vec3, FLOAT_VEC3, "vec3";
+    Vec4, FLOAT_VEC4, "vec4";
+    Int, INT, "int";
+    IVec2, INT_VEC2, "ivec2";
+    IVec3, INT_VEC3, "ivec3";
+    IVec4, INT_VEC4, "ivec4";
+    UInt, UNSIGNED_INT, "uint";
+    UVec2, UNSIGNED_INT_VEC2, "uvec2";
+    UVec3, UNSIGNED_INT_VEC3, "uvec3";
+    UVec4, UNSIGNED_INT_VEC4, "uvec4";
+    Bool, BOOL, "bool";
+    BVec2, BOOL_VEC2, "bvec2";
+    BVec3, BOOL_VEC3, "bvec3";
+    BVec4, BOOL_VEC4, "bvec4";
+    Mat2, FLOAT_MAT2, "mat2";
+    Mat3, FLOAT_MAT3, "mat3";
+    Mat4, FLOAT_MAT4, "mat4";
+    Mat2x3, FLOAT_MAT2x3, "mat2x3";
+    Mat2x4, FLOAT_MAT2x4, "mat2x4";
+    Mat3x2, FLOAT_MAT3x2, "mat3x2";
+    Mat3x4, FLOAT_MAT3x4, "mat3x4";
+    Mat4x2, FLOAT_MAT4x2, "mat4x2";
+    Mat4x3, FLOAT_MAT4x3, "mat4x3";
+    Sampler2D, SAMPLER_2D, "sampler2D";
+    Sampler3D, SAMPLER_3D, "sampler3D";
+    SamplerCube, SAMPLER_CUBE, "samplerCube";
+    Sampler2DShadow, SAMPLER_2D_SHADOW, "sampler2DShadow";
+    Sampler2DArray, SAMPLER_2D_ARRAY, "sampler2DArray";
+    Sampler2DArrayShadow, SAMPLER_2D_ARRAY_SHADOW, "sampler2DArrayShadow";
+    SamplerCubeShadow, SAMPLER_CUBE_SHADOW, "samplerCubeShadow";
+    ISampler2D, INT_SAMPLER_2D, "isampler2D";
+    ISampler3D, INT_SAMPLER_3D, "isampler3D";
+    ISamplerCube, INT_SAMPLER_CUBE, "isamplerCube";
+    ISampler2DArray, INT_SAMPLER_2D_ARRAY, "isampler2DArray";
+    USampler2D, UNSIGNED_INT_SAMPLER_2D, "usampler2D";
+    USampler3D, UNSIGNED_INT_SAMPLER_3D, "usampler3D";
+    USamplerCube, UNSIGNED_INT_SAMPLER_CUBE, "usamplerCube";
+    USampler2DArray, UNSIGNED_INT_SAMPLER_2D_ARRAY, "usampler2DArray";
+}
+
+impl GlslType {
+    /// Check that type is one of sampler types
+    pub fn is_sampler(&self) -> bool {
+        use self::GlslType::*;
+        matches!(self,
+                 Sampler2D | Sampler3D | SamplerCube |
+                 Sampler2DShadow | Sampler2DArray | Sampler2DArrayShadow | SamplerCubeShadow |
+                 ISampler2D | ISampler3D | ISamplerCube | ISampler2DArray |
+                 USampler2D | USampler3D | USamplerCube | USampler2DArray)
+    }
+
+    /// Split vector type to scalar type and number of components
+    pub fn components(&self) -> Option<(GlslType, u8)> {
+        use self::GlslType::*;
+        Some(match self {
+            Float | Int | UInt | Bool => (*self, 1),
+            Vec2 => (Float, 2),
+            Vec3 => (Float, 3),
+            Vec4 => (Float, 4),
+            IVec2 => (Int, 2),
+            IVec3 => (Int, 3),
+            IVec4 => (Int, 4),
+            UVec2 => (UInt, 2),
+            UVec3 => (UInt, 3),
+            UVec4 => (UInt, 4),
+            BVec2 => (Bool, 2),
+            BVec3 => (Bool, 3),
+            BVec4 => (Bool, 4),
+            _ => return None,
+        })
+    }
+
+    /// Check that uniform data of this type can be loaded to uniform of declared type
+    ///
+    /// Boolean uniforms can be loaded using both integer and float data, samplers is loaded using integers.
+    pub fn loads_uniform(&self, declared: GlslType) -> bool {
+        if *self == declared {
+            return true;
+        }
+        if *self == GlslType::Int && declared.is_sampler() {
+            return true;
+        }
+        match (self.components(), declared.components()) {
+            (Some((GlslType::Float, n)), Some((GlslType::Bool, m))) |
+            (Some((GlslType::Int, n)), Some((GlslType::Bool, m))) => n == m,
+            _ => false,
+        }
+    }
+
+    /// Check that vertex data of this type can be fed to attribute of declared type
+    ///
+    /// Missing components of vector attributes is filled by defaults so data may have less components.
+    pub fn feeds_attrib(&self, declared: GlslType) -> bool {
+        if *self == declared {
+            return true;
+        }
+        match (self.components(), declared.components()) {
+            (Some((scalar, n)), Some((declared_scalar, m))) => scalar == declared_scalar && n <= m,
+            _ => false,
+        }
+    }
+}
+
+/// Active uniform of program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformInfo {
+    /// Uniform name as reported by driver
+    pub name: String,
+    /// GLSL type of uniform (type of element for arrays)
+    pub typ: GlslType,
+    /// Number of array elements (1 for non-array uniforms)
+    pub size: i32,
+}
+
+/// Active attribute of program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttribInfo {
+    /// Attribute name
+    pub name: String,
+    /// GLSL type of attribute
+    pub typ: GlslType,
+    /// Number of array elements (1 for non-array attributes)
+    pub size: i32,
+}
+
+/// Strip array subscript from variable name
+pub(crate) fn base_name(name: &str) -> &str {
+    match name.find('[') {
+        Some(pos) => &name[..pos],
+        None => name,
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    cell::Cell,
+    fs,
+    mem::replace,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver},
+    time::Duration,
+};
+use notify::{Watcher, RecommendedWatcher, RecursiveMode, DebouncedEvent};
+use super::{Result, Error, HasContext, Dialect, ShaderType, Shader, Program, Preprocessor, Relocate, preprocess::{directive, include_name}};
+
+/// Program which is rebuilt when shader files changes
+///
+/// Intended for development builds. Shader sources is read from files and processed by [`Preprocessor`],
+/// names in `#include` directives is resolved relative to directory of main shader file.
+/// Shader files and included files is watched for changes.
+pub struct ReloadableProgram<G: HasContext> {
+    program: Program<G>,
+    preprocessor: Preprocessor,
+    shaders: Vec<(ShaderType, PathBuf)>,
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    watcher: RecommendedWatcher,
+    events: Receiver<DebouncedEvent>,
+    changed: Cell<bool>,
+}
+
+impl<G: HasContext + 'static> ReloadableProgram<G> {
+    /// Create program using specified shader files
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new<P: Into<PathBuf>>(gl: &G, shaders: Vec<(ShaderType, P)>) -> Result<Self> {
+        Self::with_preprocessor(gl, Preprocessor::new(Dialect::detect(gl)), shaders)
+    }
+
+    /// Create program using specified shader files and preprocessor
+    ///
+    /// Preprocessor provides target dialect, definitions and virtual files which is preferred over real ones.
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn with_preprocessor<P: Into<PathBuf>>(gl: &G, preprocessor: Preprocessor, shaders: Vec<(ShaderType, P)>) -> Result<Self> {
+        let shaders: Vec<_> = shaders.into_iter()
+            .map(|(typ, path)| (typ, path.into()))
+            .collect();
+
+        let (sender, events) = channel();
+        let watcher = notify::watcher(sender, Duration::from_millis(100))
+            .map_err(|error| Error::Unsupported(format!("file watching: {}", error)))?;
+
+        let (files, program) = Self::build(gl, &preprocessor, &shaders);
+
+        let mut this = Self {
+            program: program?,
+            preprocessor,
+            shaders,
+            files: Vec::new(),
+            dirs: Vec::new(),
+            watcher,
+            events,
+            changed: Cell::new(false),
+        };
+
+        if let Err(error) = this.watch(files) {
+            this.del(gl);
+            return Err(error);
+        }
+
+        Ok(this)
+    }
+
+    /// Current program
+    pub fn program(&self) -> &Program<G> {
+        &self.program
+    }
+
+    /// Some of shader files was changed since last build
+    pub fn is_changed(&self) -> bool {
+        for event in self.events.try_iter() {
+            use self::DebouncedEvent::*;
+            let changed = match &event {
+                Create(path) | Write(path) | Remove(path) => self.files.contains(path),
+                Rename(from, to) => self.files.contains(from) || self.files.contains(to),
+                Rescan | Error(..) => true,
+                NoticeWrite(_) | NoticeRemove(_) | Chmod(_) => false,
+            };
+            if changed {
+                self.changed.set(true);
+            }
+        }
+        self.changed.get()
+    }
+
+    /// Rebuild program when shader files was changed
+    ///
+    /// Bindings is relocated to the new program. Returns `true` when program was rebuilt.
+    /// When build fails the old program is kept and error is returned,
+    /// next attempt will be made only after files changes again.
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn reload<B: Relocate<G>>(&mut self, gl: &G, bindings: &mut B) -> Result<bool> {
+        if !self.is_changed() {
+            return Ok(false);
+        }
+        self.changed.set(false);
+
+        let (files, program) = Self::build(gl, &self.preprocessor, &self.shaders);
+        // includes may be changed even when build fails
+        let watched = self.watch(files);
+        let program = program?;
+
+        if let Err(error) = watched.and_then(|_| bindings.relocate(gl, &program)) {
+            let _ = bindings.relocate(gl, &self.program);
+            program.del(gl);
+            return Err(error);
+        }
+
+        replace(&mut self.program, program).del(gl);
+
+        Ok(true)
+    }
+
+    /// Delete program
+    pub fn del(self, gl: &G) {
+        self.program.del(gl);
+    }
+
+    /// Watch directories of specified files
+    fn watch(&mut self, files: Vec<PathBuf>) -> Result<()> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for dir in files.iter().filter_map(|file| file.parent()) {
+            if !dirs.iter().any(|known| known == dir) {
+                dirs.push(dir.into());
+            }
+        }
+
+        let watcher = &mut self.watcher;
+        self.dirs.retain(|dir| dirs.contains(dir) || {
+            let _ = watcher.unwatch(dir);
+            false
+        });
+        for dir in dirs {
+            if !self.dirs.contains(&dir) {
+                watcher.watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(|error| Error::Read {
+                        path: dir.display().to_string(),
+                        reason: error.to_string(),
+                    })?;
+                self.dirs.push(dir);
+            }
+        }
+
+        self.files = files;
+        Ok(())
+    }
+
+    /// Build program and get files which was read
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    fn build(gl: &G, preprocessor: &Preprocessor, shaders: &[(ShaderType, PathBuf)]) -> (Vec<PathBuf>, Result<Program<G>>) {
+        let mut files = Vec::new();
+
+        let sources = shaders.iter()
+            .map(|(typ, path)| {
+                let name = path.display().to_string();
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                let preprocessor = read_file(preprocessor.clone(), name.clone(), path, dir, &mut files)?;
+                Ok((*typ, preprocessor.process(*typ, &name)?))
+            })
+            .collect::<Result<Vec<_>>>();
+
+        let program = sources.and_then(|sources| Program::new(gl, Shader::new_all(gl, &sources)?));
+
+        (files, program)
+    }
+}
+
+/// Add file and files which it includes to virtual file set of preprocessor
+fn read_file(preprocessor: Preprocessor, name: String, path: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<Preprocessor> {
+    // missing files is watched too, so creating them triggers rebuild
+    let absolute = absolute(path);
+    if !files.contains(&absolute) {
+        files.push(absolute);
+    }
+
+    let source = fs::read_to_string(path)
+        .map_err(|error| Error::Read {
+            path: path.display().to_string(),
+            reason: error.to_string(),
+        })?;
+
+    let includes: Vec<String> = source.lines()
+        .filter_map(directive)
+        .filter(|(directive, _)| *directive == "include")
+        .filter_map(|(_, args)| include_name(args))
+        .map(String::from)
+        .collect();
+
+    let mut preprocessor = preprocessor.with_file(name, source);
+    for include in includes {
+        if !preprocessor.has_file(&include) {
+            let path = dir.join(&include);
+            preprocessor = read_file(preprocessor, include, &path, dir, files)?;
+        }
+    }
+
+    Ok(preprocessor)
+}
+
+/// Absolute path in the form which is reported by watcher
+fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| match (path.parent(), path.file_name()) {
+            (Some(dir), Some(name)) => fs::canonicalize(if dir.as_os_str().is_empty() { Path::new(".") } else { dir })
+                .map(|dir| dir.join(name)),
+            _ => Err(std::io::ErrorKind::NotFound.into()),
+        })
+        .unwrap_or_else(|_| path.into())
+}
@@ -0,0 +1,78 @@
+use super::{Result, Error, HasContext, Program, AsUniform, Uniform, AsAttrib, Attrib, Vertex, VertexAttribs};
+
+/// The trait for bindings which can be resolved again after program relinking
+///
+/// Uniforms and attributes which is no longer active become unbound instead of failing,
+/// because they may be optimized out by compiler after shader changes.
+pub trait Relocate<G: HasContext> {
+    /// Resolve locations using specified program
+    fn relocate(&mut self, gl: &G, program: &Program<G>) -> Result<()>;
+}
+
+impl<G: HasContext, T: AsUniform<G>> Relocate<G> for Uniform<G, T> {
+    fn relocate(&mut self, gl: &G, program: &Program<G>) -> Result<()> {
+        self.location = match program.uniform::<T, _>(gl, &self.name) {
+            Ok(uniform) => uniform.location,
+            Err(Error::NoUniform(_)) => None,
+            Err(error) => return Err(error),
+        };
+        Ok(())
+    }
+}
+
+impl<G: HasContext, T: AsAttrib<G>> Relocate<G> for Attrib<G, T> {
+    fn relocate(&mut self, gl: &G, program: &Program<G>) -> Result<()> {
+        self.attrib = match program.attrib::<T, _>(gl, &self.name) {
+            Ok(attrib) => attrib.attrib,
+            Err(Error::NoAttrib(_)) => None,
+            Err(error) => return Err(error),
+        };
+        Ok(())
+    }
+}
+
+impl<G: HasContext, V: Vertex<G>> Relocate<G> for VertexAttribs<G, V> {
+    fn relocate(&mut self, gl: &G, program: &Program<G>) -> Result<()> {
+        let active = program.active_attribs(gl);
+
+        self.attribs = V::fields().into_iter()
+            .filter_map(|field| match program.attrib_location(gl, &active, field.name, field.typ) {
+                Ok(location) => Some(Ok((location, field))),
+                Err(Error::NoAttrib(_)) => None,
+                Err(error) => Some(Err(error)),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(())
+    }
+}
+
+impl<G: HasContext> Relocate<G> for () {
+    fn relocate(&mut self, _gl: &G, _program: &Program<G>) -> Result<()> {
+        Ok(())
+    }
+}
+
+macro_rules! relocate_impls {
+    ($($($Tx: ident, $x: tt),+;)*) => {
+        $(
+            impl<G: HasContext, $($Tx: Relocate<G>),+> Relocate<G> for ($($Tx),+) {
+                fn relocate(&mut self, gl: &G, program: &Program<G>) -> Result<()> {
+                    $(self.$x.relocate(gl, program)?;)+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+relocate_impls! {
+    T0, 0, T1, 1;
+    T0, 0, T1, 1, T2, 2;
+    T0, 0, T1, 1, T2, 2, T3, 3;
+    T0, 0, T1, 1, T2, 2, T3, 3, T4, 4;
+    T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5;
+    T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6;
+    T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7;
+    T0, 0, T1, 1, T2, 2, T3, 3, T4, 4, T5, 5, T6, 6, T7, 7, T8, 8;
+}
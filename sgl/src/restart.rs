@@ -0,0 +1,77 @@
+use super::{Result, Error, GL, HasContext, Api, Geometry, DrawMode, context::Current};
+
+/// Indexed geometry with primitive restart
+///
+/// The maximum value of index type ([`AsElement::RESTART`](super::AsElement::RESTART))
+/// ends current strip, loop or fan and starts new one, so several of them can be drawn at once.
+///
+/// Requires OpenGL ES 3.0+, OpenGL 4.3+ or WebGL 2.0.
+pub struct PrimitiveRestart<D> {
+    geometry: D,
+    // WebGL always restarts primitives and doesn't allow to toggle it
+    toggle: bool,
+}
+
+impl<D> PrimitiveRestart<D> {
+    /// Wrap geometry
+    pub fn new<G: HasContext>(gl: &G, geometry: D) -> Result<Self> {
+        let caps = &Current::get(gl).caps;
+        let toggle = match (caps.api, caps.version) {
+            (Api::Gl, version) if version >= (4, 3) => true,
+            (Api::Gles, version) if version >= (3, 0) => true,
+            (Api::WebGl, version) if version >= (2, 0) => false,
+            _ => return Err(Error::Unsupported("primitive restart".into())),
+        };
+        Ok(Self { geometry, toggle })
+    }
+
+    /// Underlying geometry
+    pub fn geometry(&self) -> &D {
+        &self.geometry
+    }
+
+    /// Get back geometry
+    pub fn into_inner(self) -> D {
+        self.geometry
+    }
+}
+
+impl<G: HasContext, D: Geometry<G>> Geometry<G> for PrimitiveRestart<D> {
+    fn bind(&self, gl: &G) {
+        self.geometry.bind(gl);
+        if self.toggle {
+            unsafe { gl.enable(GL::PRIMITIVE_RESTART_FIXED_INDEX); }
+        }
+    }
+    fn unbind(&self, gl: &G) {
+        if self.toggle {
+            unsafe { gl.disable(GL::PRIMITIVE_RESTART_FIXED_INDEX); }
+        }
+        self.geometry.unbind(gl);
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        self.geometry.draw_bound(gl, mode, offset, count);
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        self.geometry.draw_bound_instanced(gl, mode, offset, count, instances);
+    }
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        self.geometry.draw_bound_base_vertex(gl, mode, offset, count, base_vertex);
+    }
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        self.geometry.draw_bound_instanced_base_vertex(gl, mode, offset, count, instances, base_vertex);
+    }
+    fn length(&self) -> i32 {
+        self.geometry.length()
+    }
+    fn is_instanced(&self) -> bool {
+        self.geometry.is_instanced()
+    }
+    fn is_indexed(&self) -> bool {
+        self.geometry.is_indexed()
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32, _restart: bool) -> Result<()> {
+        self.geometry.validate(offset, count, instances, base_vertex, true)
+    }
+}
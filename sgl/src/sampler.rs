@@ -0,0 +1,307 @@
+use super::{Result, Error, Object, GL, HasContext, Api, Capabilities, Resource, DeletionQueue, context::Current, track::Track};
+
+/// Texture filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Filter {
+    /// Take nearest texel (good for pixel art)
+    Nearest,
+    /// Interpolate between nearest texels
+    Linear,
+}
+
+/// Filter between mipmap levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MipFilter {
+    /// Mipmaps isn't used
+    None,
+    /// Take nearest level
+    Nearest,
+    /// Interpolate between nearest levels
+    Linear,
+}
+
+/// Texture coordinates wrapping mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Wrap {
+    Repeat = GL::REPEAT,
+    MirroredRepeat = GL::MIRRORED_REPEAT,
+    ClampToEdge = GL::CLAMP_TO_EDGE,
+}
+
+/// Compare function for depth textures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum CompareFunc {
+    Never = GL::NEVER,
+    Less = GL::LESS,
+    Equal = GL::EQUAL,
+    LessEqual = GL::LEQUAL,
+    Greater = GL::GREATER,
+    NotEqual = GL::NOTEQUAL,
+    GreaterEqual = GL::GEQUAL,
+    Always = GL::ALWAYS,
+}
+
+/// Texture sampling parameters
+///
+/// Default state uses linear filter without mipmaps and clamps coordinates to edge,
+/// which works with any texture size everywhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplerState {
+    /// Filter for minified texture
+    pub min_filter: Filter,
+    /// Filter for magnified texture
+    pub mag_filter: Filter,
+    /// Filter between mipmap levels
+    pub mip_filter: MipFilter,
+    /// Wrapping of S coordinate
+    pub wrap_s: Wrap,
+    /// Wrapping of T coordinate
+    pub wrap_t: Wrap,
+    /// Wrapping of R coordinate
+    pub wrap_r: Wrap,
+    /// Maximum anisotropy (`1.0` means disabled)
+    ///
+    /// Applied only when `EXT_texture_filter_anisotropic` extension is supported.
+    pub anisotropy: f32,
+    /// Compare function for depth textures
+    pub compare: Option<CompareFunc>,
+}
+
+impl Default for SamplerState {
+    fn default() -> Self {
+        Self {
+            min_filter: Filter::Linear,
+            mag_filter: Filter::Linear,
+            mip_filter: MipFilter::None,
+            wrap_s: Wrap::ClampToEdge,
+            wrap_t: Wrap::ClampToEdge,
+            wrap_r: Wrap::ClampToEdge,
+            anisotropy: 1.0,
+            compare: None,
+        }
+    }
+}
+
+impl SamplerState {
+    /// Create default sampler state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set both min and mag filters
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.min_filter = filter;
+        self.mag_filter = filter;
+        self
+    }
+
+    /// Set filter for minified texture
+    pub fn with_min_filter(mut self, filter: Filter) -> Self {
+        self.min_filter = filter;
+        self
+    }
+
+    /// Set filter for magnified texture
+    pub fn with_mag_filter(mut self, filter: Filter) -> Self {
+        self.mag_filter = filter;
+        self
+    }
+
+    /// Set filter between mipmap levels
+    pub fn with_mip_filter(mut self, filter: MipFilter) -> Self {
+        self.mip_filter = filter;
+        self
+    }
+
+    /// Set wrapping of all coordinates
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap_s = wrap;
+        self.wrap_t = wrap;
+        self.wrap_r = wrap;
+        self
+    }
+
+    /// Set wrapping of each coordinate
+    pub fn with_wraps(mut self, wrap_s: Wrap, wrap_t: Wrap, wrap_r: Wrap) -> Self {
+        self.wrap_s = wrap_s;
+        self.wrap_t = wrap_t;
+        self.wrap_r = wrap_r;
+        self
+    }
+
+    /// Set maximum anisotropy
+    pub fn with_anisotropy(mut self, anisotropy: f32) -> Self {
+        self.anisotropy = anisotropy;
+        self
+    }
+
+    /// Set compare function for depth textures
+    pub fn with_compare(mut self, compare: CompareFunc) -> Self {
+        self.compare = Some(compare);
+        self
+    }
+
+    /// Mipmaps is needed for sampling
+    pub fn has_mipmaps(&self) -> bool {
+        self.mip_filter != MipFilter::None
+    }
+
+    /// Check that state can be used with depth texture
+    ///
+    /// Depth textures can be filtered linearly only with compare function.
+    pub fn check_depth(&self) -> Result<()> {
+        let linear = self.min_filter == Filter::Linear ||
+            self.mag_filter == Filter::Linear ||
+            self.mip_filter == MipFilter::Linear;
+        if linear && self.compare.is_none() {
+            return Err(Error::Unsupported("linear filtering of depth texture without compare function".into()));
+        }
+        Ok(())
+    }
+
+    /// Check that state can be used with texture of non power of two size
+    ///
+    /// OpenGL ES 2.0 and WebGL 1.0 allows only clamping to edge without mipmaps for such textures.
+    pub fn check_npot(&self, api: Api, version: (u8, u8)) -> Result<()> {
+        let limited = match api {
+            Api::Gles => version < (3, 0),
+            Api::WebGl => version < (2, 0),
+            Api::Gl => false,
+        };
+        if limited {
+            if self.wrap_s != Wrap::ClampToEdge || self.wrap_t != Wrap::ClampToEdge {
+                return Err(Error::Unsupported("wrapping of non power of two texture".into()));
+            }
+            if self.has_mipmaps() {
+                return Err(Error::Unsupported("mipmaps of non power of two texture".into()));
+            }
+        }
+        Ok(())
+    }
+
+    fn gl_min_filter(&self) -> u32 {
+        use self::{Filter::*, MipFilter as Mip};
+        match (self.min_filter, self.mip_filter) {
+            (Nearest, Mip::None) => GL::NEAREST,
+            (Linear, Mip::None) => GL::LINEAR,
+            (Nearest, Mip::Nearest) => GL::NEAREST_MIPMAP_NEAREST,
+            (Linear, Mip::Nearest) => GL::LINEAR_MIPMAP_NEAREST,
+            (Nearest, Mip::Linear) => GL::NEAREST_MIPMAP_LINEAR,
+            (Linear, Mip::Linear) => GL::LINEAR_MIPMAP_LINEAR,
+        }
+    }
+
+    fn gl_mag_filter(&self) -> u32 {
+        match self.mag_filter {
+            Filter::Nearest => GL::NEAREST,
+            Filter::Linear => GL::LINEAR,
+        }
+    }
+
+    /// Apply filters and wrapping of S and T coordinates which is supported everywhere
+    pub(super) fn apply_basic(&self, set_i32: impl Fn(u32, i32)) {
+        set_i32(GL::TEXTURE_MIN_FILTER, self.gl_min_filter() as i32);
+        set_i32(GL::TEXTURE_MAG_FILTER, self.gl_mag_filter() as i32);
+        set_i32(GL::TEXTURE_WRAP_S, self.wrap_s as i32);
+        set_i32(GL::TEXTURE_WRAP_T, self.wrap_t as i32);
+    }
+
+    /// Apply state using parameter setters
+    pub(super) fn apply(&self, caps: &Capabilities, set_i32: impl Fn(u32, i32), set_f32: impl Fn(u32, f32)) {
+        self.apply_basic(&set_i32);
+
+        let modern = caps.is_version(Api::Gl, 3, 0) || caps.is_version(Api::Gles, 3, 0) || caps.is_version(Api::WebGl, 2, 0);
+
+        if modern {
+            set_i32(GL::TEXTURE_WRAP_R, self.wrap_r as i32);
+        }
+
+        if caps.has_extension("EXT_texture_filter_anisotropic") || caps.has_extension("ARB_texture_filter_anisotropic") ||
+            caps.is_version(Api::Gl, 4, 6) {
+            set_f32(GL::TEXTURE_MAX_ANISOTROPY_EXT, self.anisotropy);
+        }
+
+        if modern {
+            if let Some(compare) = self.compare {
+                set_i32(GL::TEXTURE_COMPARE_MODE, GL::COMPARE_REF_TO_TEXTURE as i32);
+                set_i32(GL::TEXTURE_COMPARE_FUNC, compare as i32);
+            } else {
+                set_i32(GL::TEXTURE_COMPARE_MODE, GL::NONE as i32);
+            }
+        }
+    }
+}
+
+/// Sampler object
+///
+/// Overrides sampling parameters of texture bound to the same unit.
+/// Requires OpenGL ES 3.0+, OpenGL 3.3+ or WebGL 2.0.
+pub struct Sampler<G: HasContext> {
+    sampler: G::Sampler,
+    state: SamplerState,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
+}
+
+impl<G: HasContext> Sampler<G> {
+    /// Create sampler with specified state
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G, state: SamplerState) -> Result<Self> where G: 'static {
+        let current = Current::get(gl);
+        let caps = &current.caps;
+        if !(caps.is_version(Api::Gl, 3, 3) || caps.is_version(Api::Gles, 3, 0) || caps.is_version(Api::WebGl, 2, 0)) {
+            return Err(Error::Unsupported("sampler objects".into()));
+        }
+
+        let sampler = unsafe { gl.create_sampler() }
+            .map_err(|error| Error::Create(Object::Sampler, error))?;
+
+        state.apply(caps,
+                    |name, value| unsafe { gl.sampler_parameter_i32(sampler, name, value) },
+                    |name, value| unsafe { gl.sampler_parameter_f32(sampler, name, value) });
+
+        Ok(Self { sampler, state, queue: DeletionQueue::current(), track: Track::new(Object::Sampler) })
+    }
+
+    /// Attach sampler to deletion queue
+    ///
+    /// Attached sampler is scheduled for deletion when dropped.
+    /// New sampler is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
+    }
+
+    /// Delete sampler
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
+        unsafe { gl.delete_sampler(self.sampler); }
+    }
+
+    /// Sampling parameters
+    pub fn state(&self) -> &SamplerState {
+        &self.state
+    }
+
+    /// Bind sampler to texture unit
+    pub fn bind(&self, gl: &G, unit: u32) {
+        unsafe { gl.bind_sampler(unit, Some(self.sampler)); }
+    }
+
+    /// Unbind sampler from texture unit
+    pub fn unbind(&self, gl: &G, unit: u32) {
+        unsafe { gl.bind_sampler(unit, None); }
+    }
+}
+
+impl<G: HasContext> Drop for Sampler<G> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Sampler(self.sampler));
+            self.track.release();
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use core::{
+    marker::PhantomData,
+    mem::size_of,
+    ptr::copy_nonoverlapping,
+};
+use super::{Result, GL, HasContext, Api, Attribs, Buffer, Array, Usage, DeletionQueue, Geometry, DrawMode, Pod, as_bytes, context::Current};
+#[cfg(feature = "validate")]
+use super::validate;
+
+/// Ring buffer for vertex data which is regenerated every frame
+///
+/// Data is sub-allocated sequentially. When ring is exhausted the storage is orphaned
+/// and allocation starts over, so the driver doesn't need to wait for pending draws.
+/// On OpenGL ES 3.0+ and OpenGL 3.0+ data is written using unsynchronized buffer mapping,
+/// otherwise it is uploaded using `glBufferSubData`.
+pub struct StreamBuffer<G: HasContext, T: Pod> {
+    buffer: Buffer<G, Array<T>>,
+    capacity: usize,
+    cursor: usize,
+    mapping: bool,
+}
+
+impl<G: HasContext, T: Pod> StreamBuffer<G, T> {
+    /// Create ring buffer with capacity in elements
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G, capacity: usize) -> Result<Self> where G: 'static {
+        let caps = &Current::get(gl).caps;
+        let mapping = match (caps.api, caps.version) {
+            (Api::Gl, version) | (Api::Gles, version) => version >= (3, 0),
+            (Api::WebGl, _) => false,
+        };
+
+        let mut buffer = Buffer::new(gl)?.with_usage(Usage::Stream);
+        buffer.resize(gl, capacity);
+
+        Ok(Self { buffer, capacity, cursor: 0, mapping })
+    }
+
+    /// Attach ring buffer to deletion queue
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.buffer = self.buffer.attach(queue);
+        self
+    }
+
+    /// Delete ring buffer
+    pub fn del(self, gl: &G) {
+        self.buffer.del(gl);
+    }
+
+    /// Capacity in elements
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of elements which can be allocated before orphaning
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.cursor
+    }
+
+    /// Allocate space and write data to it
+    ///
+    /// NOTE: Slices which is allocated before orphaning become invalid,
+    /// so it should be drawn before ring is exhausted (usually in the same frame).
+    pub fn push(&mut self, gl: &G, data: &[T]) -> StreamSlice<G, T> {
+        let length = data.len();
+
+        if length > self.capacity {
+            panic!("Attempt to exceed the capacity {} by length {}", self.capacity, length);
+        }
+
+        let orphan = self.cursor + length > self.capacity;
+        if orphan {
+            self.cursor = 0;
+        }
+
+        let offset = (size_of::<T>() * self.cursor) as i32;
+        let raw = as_bytes(data);
+        let size = raw.len();
+
+        unsafe {
+            gl.bind_buffer(GL::ARRAY_BUFFER, Some(self.buffer.buffer));
+
+            let written = self.mapping && {
+                let access = GL::MAP_WRITE_BIT | GL::MAP_UNSYNCHRONIZED_BIT | if orphan {
+                    GL::MAP_INVALIDATE_BUFFER_BIT
+                } else {
+                    GL::MAP_INVALIDATE_RANGE_BIT
+                };
+                let ptr = gl.map_buffer_range(GL::ARRAY_BUFFER, offset, size as i32, access);
+                if ptr.is_null() {
+                    false
+                } else {
+                    copy_nonoverlapping(raw.as_ptr(), ptr, size);
+                    gl.unmap_buffer(GL::ARRAY_BUFFER);
+                    true
+                }
+            };
+
+            if !written {
+                if orphan {
+                    gl.buffer_data_size(GL::ARRAY_BUFFER, (size_of::<T>() * self.capacity) as i32, Usage::Stream as u32);
+                }
+                gl.buffer_sub_data_u8_slice(GL::ARRAY_BUFFER, offset, raw);
+            }
+
+            gl.bind_buffer(GL::ARRAY_BUFFER, None);
+        }
+
+        let slice = StreamSlice {
+            buffer: self.buffer.buffer,
+            first: self.cursor as i32,
+            length: length as i32,
+            _type: PhantomData,
+        };
+        self.cursor += length;
+        slice
+    }
+}
+
+/// Part of ring buffer which holds pushed data
+///
+/// Can be drawn with attributes like a buffer: `(slice, &attribs).draw(gl, mode)`.
+pub struct StreamSlice<G: HasContext, T> {
+    buffer: G::Buffer,
+    first: i32,
+    length: i32,
+    _type: PhantomData<T>,
+}
+
+impl<G: HasContext, T> Clone for StreamSlice<G, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G: HasContext, T> Copy for StreamSlice<G, T> {}
+
+impl<G: HasContext, T> StreamSlice<G, T> {
+    /// Index of first element in ring buffer
+    pub fn first(&self) -> usize {
+        self.first as usize
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.length as usize
+    }
+
+    /// Slice has no elements
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+}
+
+impl<G: HasContext, A: Attribs<G>, T> Geometry<G> for (StreamSlice<G, T>, A) {
+    fn bind(&self, gl: &G) {
+        unsafe { gl.bind_buffer(GL::ARRAY_BUFFER, Some(self.0.buffer)); }
+        self.1.enable_attribs(gl);
+    }
+    fn unbind(&self, gl: &G) {
+        self.1.disable_attribs(gl);
+        unsafe { gl.bind_buffer(GL::ARRAY_BUFFER, None); }
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        unsafe { gl.draw_arrays(mode as u32, self.0.first + offset, count); }
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        unsafe { Current::get(gl).draw_arrays_instanced(gl, mode as u32, self.0.first + offset, count, instances); }
+    }
+    fn length(&self) -> i32 {
+        self.0.length
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, _instances: Option<i32>, base_vertex: i32, _restart: bool) -> Result<()> {
+        let vertices = validate::vertices::<T>(self.0.length, self.1.stride())?;
+        validate::check_vertices(offset + base_vertex, count, vertices)
+    }
+}
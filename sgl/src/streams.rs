@@ -0,0 +1,146 @@
+use super::{GL, HasContext, Attribs, Buffer, Array, ElementArray, AsElement, Geometry, DrawMode, Pod, element_offset, context::Current};
+#[cfg(feature = "validate")]
+use super::{Result, validate};
+
+/// The trait for buffers with attributes which can be bound as vertex data stream
+pub trait VertexStream<G: HasContext> {
+    /// Enable attributes and setup attribute pointers to buffer
+    fn enable_stream(&self, gl: &G);
+
+    /// Disable attributes
+    fn disable_stream(&self, gl: &G);
+
+    /// Set divisor of attributes
+    fn set_stream_divisor(&self, gl: &G, divisor: u32);
+
+    /// The full stream length in elements
+    fn stream_length(&self) -> i32;
+
+    /// The number of vertices in stream checking that buffers matches attributes
+    #[cfg(feature = "validate")]
+    fn stream_vertices(&self) -> Result<i32> {
+        Ok(self.stream_length())
+    }
+}
+
+impl<G: HasContext, A: Attribs<G>, T: Pod> VertexStream<G> for (Buffer<G, Array<T>>, A) {
+    fn enable_stream(&self, gl: &G) {
+        unsafe { gl.bind_buffer(GL::ARRAY_BUFFER, Some(self.0.buffer)); }
+        self.1.enable_attribs(gl);
+        unsafe { gl.bind_buffer(GL::ARRAY_BUFFER, None); }
+    }
+    fn disable_stream(&self, gl: &G) {
+        self.1.disable_attribs(gl);
+    }
+    fn set_stream_divisor(&self, gl: &G, divisor: u32) {
+        self.1.set_divisor(gl, divisor);
+    }
+    fn stream_length(&self) -> i32 {
+        self.0.length
+    }
+    #[cfg(feature = "validate")]
+    fn stream_vertices(&self) -> Result<i32> {
+        validate::vertices::<T>(self.0.length, self.1.stride())
+    }
+}
+
+/// Vertex data which is pulled from several buffers
+///
+/// Holds tuple of vertex streams, like `Streams(((positions, position), (colors, color)))`,
+/// so each stream can be updated separately. Drawn length is the length of the shortest stream.
+pub struct Streams<S>(pub S);
+
+macro_rules! streams_impls {
+    ($($($Sx: ident, $x: tt),+;)*) => {
+        $(
+            impl<G: HasContext, $($Sx: VertexStream<G>),+> VertexStream<G> for Streams<($($Sx),+)> {
+                fn enable_stream(&self, gl: &G) {
+                    $((self.0).$x.enable_stream(gl);)+
+                }
+                fn disable_stream(&self, gl: &G) {
+                    $((self.0).$x.disable_stream(gl);)+
+                }
+                fn set_stream_divisor(&self, gl: &G, divisor: u32) {
+                    $((self.0).$x.set_stream_divisor(gl, divisor);)+
+                }
+                fn stream_length(&self) -> i32 {
+                    let length = i32::MAX;
+                    $(let length = length.min((self.0).$x.stream_length());)+
+                    length
+                }
+                #[cfg(feature = "validate")]
+                fn stream_vertices(&self) -> Result<i32> {
+                    let length = i32::MAX;
+                    $(let length = length.min((self.0).$x.stream_vertices()?);)+
+                    Ok(length)
+                }
+            }
+        )*
+    };
+}
+
+streams_impls! {
+    S0, 0, S1, 1;
+    S0, 0, S1, 1, S2, 2;
+    S0, 0, S1, 1, S2, 2, S3, 3;
+    S0, 0, S1, 1, S2, 2, S3, 3, S4, 4;
+    S0, 0, S1, 1, S2, 2, S3, 3, S4, 4, S5, 5;
+    S0, 0, S1, 1, S2, 2, S3, 3, S4, 4, S5, 5, S6, 6;
+    S0, 0, S1, 1, S2, 2, S3, 3, S4, 4, S5, 5, S6, 6, S7, 7;
+}
+
+impl<G: HasContext, S> Geometry<G> for Streams<S> where Self: VertexStream<G> {
+    fn bind(&self, gl: &G) {
+        self.enable_stream(gl);
+    }
+    fn unbind(&self, gl: &G) {
+        self.disable_stream(gl);
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        unsafe { gl.draw_arrays(mode as u32, offset, count); }
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        unsafe { Current::get(gl).draw_arrays_instanced(gl, mode as u32, offset, count, instances); }
+    }
+    fn length(&self) -> i32 {
+        self.stream_length()
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, _instances: Option<i32>, base_vertex: i32, _restart: bool) -> Result<()> {
+        validate::check_vertices(offset + base_vertex, count, self.stream_vertices()?)
+    }
+}
+
+impl<G: HasContext, S, I: AsElement> Geometry<G> for (Streams<S>, Buffer<G, ElementArray<I>>) where Streams<S>: VertexStream<G> {
+    fn bind(&self, gl: &G) {
+        self.0.enable_stream(gl);
+        unsafe { gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(self.1.buffer)); }
+    }
+    fn unbind(&self, gl: &G) {
+        self.0.disable_stream(gl);
+        unsafe { gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, None); }
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        unsafe { gl.draw_elements(mode as u32, count, I::TYPE, element_offset::<I>(offset)); }
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        unsafe { Current::get(gl).draw_elements_instanced::<G, I>(gl, mode as u32, offset, count, instances); }
+    }
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        unsafe { Current::get(gl).draw_elements_base_vertex::<G, I>(gl, mode as u32, offset, count, base_vertex); }
+    }
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        unsafe { Current::get(gl).draw_elements_instanced_base_vertex::<G, I>(gl, mode as u32, offset, count, instances, base_vertex); }
+    }
+    fn length(&self) -> i32 {
+        self.1.length
+    }
+    fn is_indexed(&self) -> bool {
+        true
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, _instances: Option<i32>, base_vertex: i32, restart: bool) -> Result<()> {
+        let indices = self.1.shadow.as_deref().unwrap_or_default();
+        validate::check_indices(indices, offset, count, restart.then(|| I::RESTART.into()), base_vertex, self.0.stream_vertices()?)
+    }
+}
@@ -1,22 +1,33 @@
 use core::{
+    cell::Cell,
     marker::PhantomData,
     mem::size_of,
-    slice::from_raw_parts,
 };
-use super::{Result, GL, HasContext, Uniform};
+use super::{Result, Error, Object, GL, HasContext, Api, Uniform, Resource, DeletionQueue, Pod, as_bytes, Rgb8, Rgba8, La8, Rg8, Rgba16F, Rgba32F, Depth24Stencil8, Framebuffer, Image, SamplerState, Filter, Capabilities, context::Current, track::Track};
 
 pub trait TextureTarget {
     /// OpenGL texture target
     const TARGET: u32;
 
+    /// Target isn't available on every context
+    const OPTIONAL: bool = false;
+
     /// Texture image size type
     type Coords: Copy + IsPow2;
 
+    /// Check that target can be used with context
+    fn is_supported(_caps: &Capabilities) -> bool {
+        true
+    }
+
+    /// Size of mipmap level
+    fn level_size(size: Self::Coords, level: usize) -> Self::Coords;
+
     /// Load image data to texture
-    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>);
+    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) -> Result<()>;
 
     /// Load image data to texture
-    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>);
+    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) -> Result<()>;
 }
 
 pub struct Texture2D;
@@ -25,46 +36,16 @@ impl TextureTarget for Texture2D {
     const TARGET: u32 = GL::TEXTURE_2D;
     type Coords = (usize, usize);
 
-    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) {
-        if let Some(raw) = &data {
-            if size.0 * size.1 * size_of::<F::Pixel>() != raw.len() {
-                panic!("Texture image data size mismatch");
-            }
-        }
-        unsafe {
-            gl.tex_image_2d(
-                Self::TARGET,
-                level,
-                F::FORMAT as i32,
-                size.0 as i32,
-                size.1 as i32,
-                0,
-                F::FORMAT,
-                F::TYPE,
-                data,
-            );
-        }
+    fn level_size(size: Self::Coords, level: usize) -> Self::Coords {
+        ((size.0 >> level).max(1), (size.1 >> level).max(1))
     }
 
-    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) {
-        if let Some(raw) = &data {
-            if size.0 * size.1 * size_of::<F::Pixel>() != raw.len() {
-                panic!("Texture sub image data size mismatch");
-            }
-        }
-        unsafe {
-            gl.tex_sub_image_2d_u8_slice(
-                Self::TARGET,
-                level,
-                off.0 as i32,
-                off.1 as i32,
-                size.0 as i32,
-                size.1 as i32,
-                F::FORMAT,
-                F::TYPE,
-                data,
-            );
-        }
+    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_image_2d::<F, G>(gl, Self::TARGET, level, size, data)
+    }
+
+    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_sub_image_2d::<F, G>(gl, Self::TARGET, level, off, size, data)
     }
 }
 
@@ -72,59 +53,313 @@ pub struct Texture3D;
 
 impl TextureTarget for Texture3D {
     const TARGET: u32 = GL::TEXTURE_3D;
+    const OPTIONAL: bool = true;
     type Coords = (usize, usize, usize);
 
-    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) {
+    fn is_supported(caps: &Capabilities) -> bool {
+        caps.api == Api::Gl ||
+            caps.is_version(Api::Gles, 3, 0) ||
+            caps.is_version(Api::WebGl, 2, 0)
+    }
+
+    fn level_size(size: Self::Coords, level: usize) -> Self::Coords {
+        ((size.0 >> level).max(1), (size.1 >> level).max(1), (size.2 >> level).max(1))
+    }
+
+    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_image_3d::<F, G>(gl, Self::TARGET, level, size, data)
+    }
+
+    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_sub_image_3d::<F, G>(gl, Self::TARGET, level, off, size, data)
+    }
+}
+
+/// Size or offset of 2D array texture image
+///
+/// Layer means number of layers for size and first layer for offset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ArrayCoords {
+    pub x: usize,
+    pub y: usize,
+    pub layer: usize,
+}
+
+impl ArrayCoords {
+    pub fn new(x: usize, y: usize, layer: usize) -> Self {
+        Self { x, y, layer }
+    }
+}
+
+pub struct Texture2DArray;
+
+impl TextureTarget for Texture2DArray {
+    const TARGET: u32 = GL::TEXTURE_2D_ARRAY;
+    const OPTIONAL: bool = true;
+    type Coords = ArrayCoords;
+
+    fn is_supported(caps: &Capabilities) -> bool {
+        caps.is_version(Api::Gl, 3, 0) ||
+            caps.is_version(Api::Gles, 3, 0) ||
+            caps.is_version(Api::WebGl, 2, 0) ||
+            caps.has_extension("EXT_texture_array")
+    }
+
+    fn level_size(size: Self::Coords, level: usize) -> Self::Coords {
+        ArrayCoords::new((size.x >> level).max(1), (size.y >> level).max(1), size.layer)
+    }
+
+    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_image_3d::<F, G>(gl, Self::TARGET, level, (size.x, size.y, size.layer), data)
+    }
+
+    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        load_sub_image_3d::<F, G>(gl, Self::TARGET, level, (off.x, off.y, off.layer), (size.x, size.y, size.layer), data)
+    }
+}
+
+/// Face of cube texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum CubeFace {
+    PositiveX = GL::TEXTURE_CUBE_MAP_POSITIVE_X,
+    NegativeX = GL::TEXTURE_CUBE_MAP_NEGATIVE_X,
+    PositiveY = GL::TEXTURE_CUBE_MAP_POSITIVE_Y,
+    NegativeY = GL::TEXTURE_CUBE_MAP_NEGATIVE_Y,
+    PositiveZ = GL::TEXTURE_CUBE_MAP_POSITIVE_Z,
+    NegativeZ = GL::TEXTURE_CUBE_MAP_NEGATIVE_Z,
+}
+
+impl CubeFace {
+    /// All faces in order of OpenGL targets
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX, CubeFace::NegativeX,
+        CubeFace::PositiveY, CubeFace::NegativeY,
+        CubeFace::PositiveZ, CubeFace::NegativeZ,
+    ];
+}
+
+/// Size or offset of cube texture face image
+///
+/// The same region is used for each face when whole texture is loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CubeCoords {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl CubeCoords {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+pub struct TextureCube;
+
+impl TextureTarget for TextureCube {
+    const TARGET: u32 = GL::TEXTURE_CUBE_MAP;
+    type Coords = CubeCoords;
+
+    fn level_size(size: Self::Coords, level: usize) -> Self::Coords {
+        CubeCoords::new((size.x >> level).max(1), (size.y >> level).max(1))
+    }
+
+    /// Load image data of all faces
+    ///
+    /// Data contains images of faces in order of [`CubeFace::ALL`].
+    fn load_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        if size.x != size.y {
+            panic!("Cube texture faces should be square");
+        }
+        let length = image_length::<F>(size.x, size.y, 1);
         if let Some(raw) = &data {
-            if size.0 * size.1 * size.2 * size_of::<F::Pixel>() != raw.len() {
+            if length * CubeFace::ALL.len() != raw.len() {
                 panic!("Texture image data size mismatch");
             }
         }
-        unsafe {
-            gl.tex_image_3d(
-                Self::TARGET,
-                level,
-                F::FORMAT as i32,
-                size.0 as i32,
-                size.1 as i32,
-                size.2 as i32,
-                0,
-                F::FORMAT,
-                F::TYPE,
-                data,
-            );
+        for (index, face) in CubeFace::ALL.iter().enumerate() {
+            let data = data.map(|raw| &raw[index * length..(index + 1) * length]);
+            load_image_2d::<F, G>(gl, *face as u32, level, (size.x, size.y), data)?;
         }
+        Ok(())
     }
 
-    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) {
+    /// Load sub image data of all faces
+    fn load_sub_image<F: TextureFormat, G: HasContext>(gl: &G, level: i32, off: Self::Coords, size: Self::Coords, data: Option<&[u8]>) -> Result<()> {
+        let length = image_length::<F>(size.x, size.y, 1);
         if let Some(raw) = &data {
-            if size.0 * size.1 * size.2 * size_of::<F::Pixel>() != raw.len() {
+            if length * CubeFace::ALL.len() != raw.len() {
                 panic!("Texture sub image data size mismatch");
             }
         }
-        unsafe {
-            gl.tex_sub_image_3d_u8_slice(
-                Self::TARGET,
-                level,
-                off.0 as i32,
-                off.1 as i32,
-                off.2 as i32,
-                size.0 as i32,
-                size.1 as i32,
-                size.2 as i32,
-                F::FORMAT,
-                F::TYPE,
-                data,
-            );
+        for (index, face) in CubeFace::ALL.iter().enumerate() {
+            let data = data.map(|raw| &raw[index * length..(index + 1) * length]);
+            load_sub_image_2d::<F, G>(gl, *face as u32, level, (off.x, off.y), (size.x, size.y), data)?;
+        }
+        Ok(())
+    }
+}
+
+fn load_image_2d<F: TextureFormat, G: HasContext>(gl: &G, target: u32, level: i32, size: (usize, usize), data: Option<&[u8]>) -> Result<()> {
+    let length = image_length::<F>(size.0, size.1, 1);
+    if let Some(raw) = &data {
+        if length != raw.len() {
+            panic!("Texture image data size mismatch");
         }
     }
+    if F::COMPRESSED {
+        let zeroed;
+        let data = match data {
+            Some(raw) => raw,
+            None => { zeroed = vec![0; length]; &zeroed },
+        };
+        return unsafe { Current::get(gl).compressed_tex_image_2d(target, level, F::FORMAT, size, data) };
+    }
+    unsafe {
+        gl.tex_image_2d(
+            target,
+            level,
+            F::INTERNAL as i32,
+            size.0 as i32,
+            size.1 as i32,
+            0,
+            F::FORMAT,
+            F::TYPE,
+            data,
+        );
+    }
+    Ok(())
+}
+
+fn load_sub_image_2d<F: TextureFormat, G: HasContext>(gl: &G, target: u32, level: i32, off: (usize, usize), size: (usize, usize), data: Option<&[u8]>) -> Result<()> {
+    let length = image_length::<F>(size.0, size.1, 1);
+    if let Some(raw) = &data {
+        if length != raw.len() {
+            panic!("Texture sub image data size mismatch");
+        }
+    }
+    if F::COMPRESSED {
+        let zeroed;
+        let data = match data {
+            Some(raw) => raw,
+            None => { zeroed = vec![0; length]; &zeroed },
+        };
+        return unsafe { Current::get(gl).compressed_tex_sub_image_2d(target, level, off, size, F::FORMAT, data) };
+    }
+    unsafe {
+        gl.tex_sub_image_2d_u8_slice(
+            target,
+            level,
+            off.0 as i32,
+            off.1 as i32,
+            size.0 as i32,
+            size.1 as i32,
+            F::FORMAT,
+            F::TYPE,
+            data,
+        );
+    }
+    Ok(())
+}
+
+fn load_image_3d<F: TextureFormat, G: HasContext>(gl: &G, target: u32, level: i32, size: (usize, usize, usize), data: Option<&[u8]>) -> Result<()> {
+    let length = image_length::<F>(size.0, size.1, size.2);
+    if let Some(raw) = &data {
+        if length != raw.len() {
+            panic!("Texture image data size mismatch");
+        }
+    }
+    if F::COMPRESSED {
+        let zeroed;
+        let data = match data {
+            Some(raw) => raw,
+            None => { zeroed = vec![0; length]; &zeroed },
+        };
+        return unsafe { Current::get(gl).compressed_tex_image_3d(target, level, F::FORMAT, size, data) };
+    }
+    unsafe {
+        gl.tex_image_3d(
+            target,
+            level,
+            F::INTERNAL as i32,
+            size.0 as i32,
+            size.1 as i32,
+            size.2 as i32,
+            0,
+            F::FORMAT,
+            F::TYPE,
+            data,
+        );
+    }
+    Ok(())
+}
+
+fn load_sub_image_3d<F: TextureFormat, G: HasContext>(gl: &G, target: u32, level: i32, off: (usize, usize, usize), size: (usize, usize, usize), data: Option<&[u8]>) -> Result<()> {
+    let length = image_length::<F>(size.0, size.1, size.2);
+    if let Some(raw) = &data {
+        if length != raw.len() {
+            panic!("Texture sub image data size mismatch");
+        }
+    }
+    if F::COMPRESSED {
+        let zeroed;
+        let data = match data {
+            Some(raw) => raw,
+            None => { zeroed = vec![0; length]; &zeroed },
+        };
+        return unsafe { Current::get(gl).compressed_tex_sub_image_3d(target, level, off, size, F::FORMAT, data) };
+    }
+    unsafe {
+        gl.tex_sub_image_3d_u8_slice(
+            target,
+            level,
+            off.0 as i32,
+            off.1 as i32,
+            off.2 as i32,
+            size.0 as i32,
+            size.1 as i32,
+            size.2 as i32,
+            F::FORMAT,
+            F::TYPE,
+            data,
+        );
+    }
+    Ok(())
 }
 
 /// Format of texture pixels
 pub trait TextureFormat {
-    type Pixel;
+    /// Pixel type or block type of compressed format
+    type Pixel: Pod;
     const FORMAT: u32;
     const TYPE: u32;
+
+    /// Internal format of texture
+    ///
+    /// Same as format for unsized formats of OpenGL ES 2.0.
+    const INTERNAL: u32 = Self::FORMAT;
+
+    /// Format isn't available on every context
+    const OPTIONAL: bool = false;
+
+    /// Size of compressed block in pixels
+    const BLOCK: (usize, usize) = (1, 1);
+
+    /// Format is block compressed
+    ///
+    /// Compressed textures is loaded by blocks and can't have generated mipmaps.
+    const COMPRESSED: bool = false;
+
+    /// Check that format can be used with context
+    fn is_supported(_caps: &Capabilities) -> bool {
+        true
+    }
+}
+
+/// Length of image data in bytes
+pub(super) fn image_length<F: TextureFormat>(width: usize, height: usize, depth: usize) -> usize {
+    let (block_width, block_height) = F::BLOCK;
+    width.div_ceil(block_width) * height.div_ceil(block_height) * depth * size_of::<F::Pixel>()
 }
 
 macro_rules! texture_formats {
@@ -141,62 +376,243 @@ macro_rules! texture_formats {
     };
 }
 
+macro_rules! sized_texture_formats {
+    ($($(#[$meta: meta])* $name: ident, $internal: ident, $format: ident, $type: ident, $pixel: ty;)*) => {
+        $(
+            $(#[$meta])*
+            #[allow(non_camel_case_types)]
+            pub struct $name;
+
+            impl TextureFormat for $name {
+                type Pixel = $pixel;
+                const FORMAT: u32 = GL::$format;
+                const TYPE: u32 = GL::$type;
+                const INTERNAL: u32 = GL::$internal;
+                const OPTIONAL: bool = true;
+
+                fn is_supported(caps: &Capabilities) -> bool {
+                    caps.is_version(Api::Gl, 3, 0) ||
+                        caps.is_version(Api::Gles, 3, 0) ||
+                        caps.is_version(Api::WebGl, 2, 0)
+                }
+            }
+        )+
+    };
+}
+
 texture_formats! {
-    RGB888, RGB, UNSIGNED_BYTE, (u8, u8, u8);
-    RGBA8888, RGBA, UNSIGNED_BYTE, (u8, u8, u8, u8);
+    RGB888, RGB, UNSIGNED_BYTE, Rgb8;
+    RGBA8888, RGBA, UNSIGNED_BYTE, Rgba8;
     L8, LUMINANCE, UNSIGNED_BYTE, u8;
     A8, ALPHA, UNSIGNED_BYTE, u8;
-    LA88, LUMINANCE_ALPHA, UNSIGNED_BYTE, (u8, u8);
+    LA88, LUMINANCE_ALPHA, UNSIGNED_BYTE, La8;
 
     RGB565, RGB, UNSIGNED_SHORT_5_6_5, u16;
     RGBA4444, RGBA, UNSIGNED_SHORT_4_4_4_4, u16;
     RGBA5551, RGBA, UNSIGNED_SHORT_5_5_5_1, u16;
 }
 
+sized_texture_formats! {
+    /// Single 8-bit red channel
+    R8, R8, RED, UNSIGNED_BYTE, u8;
+    /// 8-bit red and green channels
+    RG8, RG8, RG, UNSIGNED_BYTE, Rg8;
+    /// Half float channels
+    ///
+    /// Rendering to it needs `EXT_color_buffer_half_float` or `EXT_color_buffer_float` on OpenGL ES.
+    RGBA16F, RGBA16F, RGBA, HALF_FLOAT, Rgba16F;
+    /// Float channels
+    ///
+    /// Rendering to it needs `EXT_color_buffer_float` and linear filtering needs `OES_texture_float_linear` on OpenGL ES.
+    RGBA32F, RGBA32F, RGBA, FLOAT, Rgba32F;
+    /// Packed depth and stencil
+    DEPTH24_STENCIL8, DEPTH24_STENCIL8, DEPTH_STENCIL, UNSIGNED_INT_24_8, Depth24Stencil8;
+    /// 8-bit sRGB color and linear alpha
+    ///
+    /// Sampling converts colors to linear space, rendering converts it back when framebuffer sRGB mode is enabled.
+    SRGB8_ALPHA8, SRGB8_ALPHA8, RGBA, UNSIGNED_BYTE, Rgba8;
+}
+
 pub struct Texture<G: HasContext, T: TextureTarget, F: TextureFormat> {
     pub(super) texture: G::Texture,
+    size: Cell<Option<T::Coords>>,
+    levels: Cell<usize>,
+    sampler: Cell<SamplerState>,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
     _phantom: PhantomData<(T, F)>,
 }
 
 impl<G: HasContext, T: TextureTarget, F: TextureFormat> Texture<G, T, F> {
     /// Create texture
-    pub fn new(gl: &G) -> Result<Self> {
-        unsafe {
-            let texture = gl.create_texture()?;
-            Ok(Self { texture, _phantom: PhantomData })
+    ///
+    /// Texture is created with default sampler state (see [`SamplerState`]),
+    /// depth textures uses nearest filter instead of linear.
+    /// Fails when target or format isn't supported by context.
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G) -> Result<Self> where G: 'static {
+        if T::OPTIONAL || F::OPTIONAL {
+            let caps = &Current::get(gl).caps;
+            if !T::is_supported(caps) {
+                return Err(Error::Unsupported(format!("texture target 0x{:04x}", T::TARGET)));
+            }
+            if !F::is_supported(caps) {
+                return Err(Error::Unsupported(format!("texture format 0x{:04x}", F::INTERNAL)));
+            }
         }
+        let texture = unsafe { gl.create_texture() }
+            .map_err(|error| Error::Create(Object::Texture, error))?;
+        let sampler = if is_depth::<F>() {
+            SamplerState::default().with_filter(Filter::Nearest)
+        } else {
+            SamplerState::default()
+        };
+        let this = Self {
+            texture,
+            size: Cell::new(None),
+            levels: Cell::new(0),
+            sampler: Cell::new(sampler),
+            queue: DeletionQueue::current(),
+            track: Track::new(Object::Texture),
+            _phantom: PhantomData,
+        };
+        this.bind_texture(gl);
+        sampler.apply_basic(|name, value| unsafe { gl.tex_parameter_i32(T::TARGET, name, value) });
+        this.unbind_texture(gl);
+        Ok(this)
+    }
+
+    /// Attach texture to deletion queue
+    ///
+    /// Attached texture is scheduled for deletion when dropped.
+    /// New texture is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.queue = Some(queue.clone());
+        self
     }
 
     /// Delete texture
-    pub fn del(self, gl: &G) {
+    pub fn del(mut self, gl: &G) {
+        self.queue = None;
+        self.track.release();
         unsafe { gl.delete_texture(self.texture); }
     }
 
-    pub fn init(&self, gl: &G, size: T::Coords) {
+    /// Size of texture image
+    ///
+    /// Returns `None` until image is initialized or loaded.
+    pub fn size(&self) -> Option<T::Coords> {
+        self.size.get()
+    }
+
+    /// Initialize image with undefined data
+    ///
+    /// Fails when sampler state can't be used with non power of two size on OpenGL ES 2.0 or WebGL 1.0.
+    pub fn init(&self, gl: &G, size: T::Coords) -> Result<()> {
+        self.check_size(gl, size)?;
         self.bind_texture(gl);
-        T::load_image::<F, G>(gl, 0, size, None);
+        let result = T::load_image::<F, G>(gl, 0, size, None);
         self.unbind_texture(gl);
+        result?;
+        self.size.set(Some(size));
+        self.levels.set(1);
+        Ok(())
     }
 
-    pub fn load(&self, gl: &G, size: T::Coords, data: &[F::Pixel]) {
+    /// Load image data
+    ///
+    /// Mipmaps is generated when sampler state of texture uses it and format isn't compressed.
+    /// Fails like [`Texture::init`].
+    pub fn load(&self, gl: &G, size: T::Coords, data: &[F::Pixel]) -> Result<()> {
+        self.check_size(gl, size)?;
         self.bind_texture(gl);
-        let raw = unsafe { from_raw_parts(
-            data as *const _ as *const u8,
-            size_of::<F::Pixel>() * data.len()
-        ) };
-        T::load_image::<F, G>(gl, 0, size, Some(raw));
-        if size.is_pow2() {
-            unsafe {
-                gl.generate_mipmap(T::TARGET);
-            }
-        } else {
-            unsafe {
-                gl.tex_parameter_i32(T::TARGET, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
-                gl.tex_parameter_i32(T::TARGET, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
-                gl.tex_parameter_i32(T::TARGET, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+        let result = T::load_image::<F, G>(gl, 0, size, Some(as_bytes(data)));
+        if result.is_ok() && self.sampler.get().has_mipmaps() && !F::COMPRESSED {
+            unsafe { gl.generate_mipmap(T::TARGET); }
+        }
+        self.unbind_texture(gl);
+        result?;
+        self.size.set(Some(size));
+        self.levels.set(1);
+        Ok(())
+    }
+
+    /// Load image data with all mipmap levels
+    ///
+    /// Each next level has half size of previous one.
+    /// Fails like [`Texture::init`].
+    pub fn load_levels(&self, gl: &G, size: T::Coords, levels: &[&[F::Pixel]]) -> Result<()> {
+        self.load_raw_levels(gl, size, levels.iter().map(|data| as_bytes(data)))
+    }
+
+    pub(super) fn load_raw_levels<'a>(&self, gl: &G, size: T::Coords, levels: impl Iterator<Item = &'a [u8]>) -> Result<()> {
+        self.check_size(gl, size)?;
+        self.bind_texture(gl);
+        let mut count = 0;
+        let result = levels.enumerate().try_for_each(|(level, data)| {
+            count += 1;
+            T::load_image::<F, G>(gl, level as i32, T::level_size(size, level), Some(data))
+        });
+        self.unbind_texture(gl);
+        result?;
+        self.size.set(Some(size));
+        self.levels.set(count);
+        Ok(())
+    }
+
+    /// Generate mipmaps from the first level of image
+    ///
+    /// Compressed formats can't have generated mipmaps.
+    pub fn generate_mipmaps(&self, gl: &G) {
+        self.bind_texture(gl);
+        unsafe { gl.generate_mipmap(T::TARGET); }
+        self.unbind_texture(gl);
+    }
+
+    /// Sampler state of texture
+    pub fn sampler(&self) -> SamplerState {
+        self.sampler.get()
+    }
+
+    /// Set sampler state of texture
+    ///
+    /// Fails when state can't be used with non power of two size of loaded image on OpenGL ES 2.0 or WebGL 1.0
+    /// or when depth texture is filtered linearly without compare function.
+    /// Generates mipmaps for loaded image when state uses it unless levels was loaded explicitly.
+    pub fn set_sampler(&self, gl: &G, state: SamplerState) -> Result<()> {
+        let current = Current::get(gl);
+        let caps = &current.caps;
+        let size = self.size.get();
+
+        if is_depth::<F>() {
+            state.check_depth()?;
+        }
+        if let Some(size) = size {
+            if !size.is_pow2() {
+                state.check_npot(caps.api, caps.version)?;
             }
         }
+
+        self.bind_texture(gl);
+        state.apply(caps,
+                    |name, value| unsafe { gl.tex_parameter_i32(T::TARGET, name, value) },
+                    |name, value| unsafe { gl.tex_parameter_f32(T::TARGET, name, value) });
+        if size.is_some() && state.has_mipmaps() && self.levels.get() == 1 && !F::COMPRESSED {
+            unsafe { gl.generate_mipmap(T::TARGET); }
+        }
         self.unbind_texture(gl);
+        self.sampler.set(state);
+
+        Ok(())
+    }
+
+    /// Check that sampler state can be used with image size
+    fn check_size(&self, gl: &G, size: T::Coords) -> Result<()> {
+        if !size.is_pow2() {
+            let caps = &Current::get(gl).caps;
+            self.sampler.get().check_npot(caps.api, caps.version)?;
+        }
+        Ok(())
     }
 
     fn bind_texture(&self, gl: &G) {
@@ -208,6 +624,67 @@ impl<G: HasContext, T: TextureTarget, F: TextureFormat> Texture<G, T, F> {
     }
 }
 
+/// Format has depth component
+fn is_depth<F: TextureFormat>() -> bool {
+    F::FORMAT == GL::DEPTH_COMPONENT || F::FORMAT == GL::DEPTH_STENCIL
+}
+
+impl<G: HasContext, F: TextureFormat> Texture<G, Texture2D, F> {
+    /// Read level of texture image back
+    ///
+    /// Uses temporary framebuffer, so texture format should be color renderable.
+    /// Fails when texture isn't initialized or loaded.
+    pub fn read(&self, gl: &G, level: i32) -> Result<Image<F::Pixel>> where G: 'static {
+        let (width, height) = self.size().ok_or(Error::Empty(Object::Texture))?;
+        let (width, height) = ((width >> level).max(1), (height >> level).max(1));
+        let framebuffer = Framebuffer::new(gl)?;
+        let result = framebuffer.attach_texture(gl, self, level)
+            .map(|_| framebuffer.read_pixels::<F>(gl, 0, 0, width, height));
+        framebuffer.del(gl);
+        result
+    }
+}
+
+impl<G: HasContext, F: TextureFormat> Texture<G, TextureCube, F> {
+    /// Load image data of single face
+    ///
+    /// Texture should be initialized or loaded before.
+    /// Mipmaps isn't generated, so call [`Texture::generate_mipmaps`] when all faces is loaded.
+    pub fn load_face(&self, gl: &G, face: CubeFace, data: &[F::Pixel]) -> Result<()> {
+        let size = self.size().expect("Texture should be initialized before loading face");
+        self.bind_texture(gl);
+        let result = load_sub_image_2d::<F, G>(gl, face as u32, 0, (0, 0), (size.x, size.y), Some(as_bytes(data)));
+        self.unbind_texture(gl);
+        result
+    }
+}
+
+impl<G: HasContext, F: TextureFormat> Texture<G, Texture2DArray, F> {
+    /// Load image data of single layer
+    ///
+    /// Texture should be initialized or loaded before.
+    /// Mipmaps isn't generated, so call [`Texture::generate_mipmaps`] when all layers is loaded.
+    pub fn load_layer(&self, gl: &G, layer: usize, data: &[F::Pixel]) -> Result<()> {
+        let size = self.size().expect("Texture should be initialized before loading layer");
+        if layer >= size.layer {
+            panic!("Texture layer out of range");
+        }
+        self.bind_texture(gl);
+        let result = load_sub_image_3d::<F, G>(gl, Texture2DArray::TARGET, 0, (0, 0, layer), (size.x, size.y, 1), Some(as_bytes(data)));
+        self.unbind_texture(gl);
+        result
+    }
+}
+
+impl<G: HasContext, T: TextureTarget, F: TextureFormat> Drop for Texture<G, T, F> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.push(Resource::Texture(self.texture));
+            self.track.release();
+        }
+    }
+}
+
 pub trait Textures<G: HasContext> {
     fn enable(&self, gl: &G);
     fn disable(&self, gl: &G);
@@ -276,6 +753,18 @@ impl IsPow2 for (usize, usize, usize) {
     }
 }
 
+impl IsPow2 for ArrayCoords {
+    fn is_pow2(&self) -> bool {
+        self.x.is_pow2() && self.y.is_pow2()
+    }
+}
+
+impl IsPow2 for CubeCoords {
+    fn is_pow2(&self) -> bool {
+        self.x.is_pow2() && self.y.is_pow2()
+    }
+}
+
 impl IsPow2 for usize {
     fn is_pow2(&self) -> bool {
         (self & (self - 1)) == 0
@@ -0,0 +1,218 @@
+#[cfg(feature = "debug-resources")]
+use std::{
+    fmt,
+    panic::Location,
+    sync::Mutex,
+    collections::BTreeMap,
+};
+use super::Object;
+
+/// Tracking record of GL object handle
+///
+/// Does nothing unless `debug-resources` feature is enabled.
+#[cfg(not(feature = "debug-resources"))]
+pub(super) struct Track;
+
+#[cfg(not(feature = "debug-resources"))]
+impl Track {
+    #[inline(always)]
+    pub(super) fn new(_object: Object) -> Self {
+        Track
+    }
+
+    #[inline(always)]
+    pub(super) fn release(&mut self) {}
+}
+
+/// Tracking record of GL object handle
+///
+/// Object which is dropped without releasing is reported as leaked.
+#[cfg(feature = "debug-resources")]
+pub(super) struct Track {
+    id: u64,
+    released: bool,
+}
+
+#[cfg(feature = "debug-resources")]
+impl Track {
+    #[track_caller]
+    pub(super) fn new(object: Object) -> Self {
+        let location = Location::caller();
+        let mut tracker = tracker();
+        let id = tracker.next_id;
+        tracker.next_id += 1;
+        tracker.stats.get_mut(object).created += 1;
+        tracker.live.insert(id, ResourceRecord { object, location });
+        Self { id, released: false }
+    }
+
+    /// Object was deleted or scheduled for deletion
+    pub(super) fn release(&mut self) {
+        if !self.released {
+            self.released = true;
+            let mut tracker = tracker();
+            if let Some(record) = tracker.live.remove(&self.id) {
+                tracker.stats.get_mut(record.object).deleted += 1;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "debug-resources")]
+impl Drop for Track {
+    fn drop(&mut self) {
+        if !self.released {
+            let mut tracker = tracker();
+            if let Some(record) = tracker.live.remove(&self.id) {
+                tracker.stats.get_mut(record.object).leaked += 1;
+                tracker.leaks.push(record);
+            }
+        }
+    }
+}
+
+/// Counters of objects of some kind
+#[cfg(feature = "debug-resources")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectStats {
+    /// Number of created objects
+    pub created: usize,
+    /// Number of deleted objects
+    pub deleted: usize,
+    /// Number of objects dropped without deletion
+    pub leaked: usize,
+}
+
+#[cfg(feature = "debug-resources")]
+impl ObjectStats {
+    const fn new() -> Self {
+        Self { created: 0, deleted: 0, leaked: 0 }
+    }
+
+    /// Number of alive objects
+    pub fn live(&self) -> usize {
+        self.created - self.deleted - self.leaked
+    }
+}
+
+/// Snapshot of GL objects statistics
+#[cfg(feature = "debug-resources")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceStats {
+    pub shaders: ObjectStats,
+    pub programs: ObjectStats,
+    pub buffers: ObjectStats,
+    pub textures: ObjectStats,
+    pub vertex_arrays: ObjectStats,
+    pub framebuffers: ObjectStats,
+    pub samplers: ObjectStats,
+}
+
+#[cfg(feature = "debug-resources")]
+impl ResourceStats {
+    const fn new() -> Self {
+        Self {
+            shaders: ObjectStats::new(),
+            programs: ObjectStats::new(),
+            buffers: ObjectStats::new(),
+            textures: ObjectStats::new(),
+            vertex_arrays: ObjectStats::new(),
+            framebuffers: ObjectStats::new(),
+            samplers: ObjectStats::new(),
+        }
+    }
+
+    /// Get current statistics
+    pub fn snapshot() -> Self {
+        tracker().stats
+    }
+
+    /// Get counters for objects of specified kind
+    pub fn get(&self, object: Object) -> &ObjectStats {
+        use self::Object::*;
+        match object {
+            Shader => &self.shaders,
+            Program => &self.programs,
+            Buffer => &self.buffers,
+            Texture => &self.textures,
+            VertexArray => &self.vertex_arrays,
+            Framebuffer => &self.framebuffers,
+            Sampler => &self.samplers,
+        }
+    }
+
+    fn get_mut(&mut self, object: Object) -> &mut ObjectStats {
+        use self::Object::*;
+        match object {
+            Shader => &mut self.shaders,
+            Program => &mut self.programs,
+            Buffer => &mut self.buffers,
+            Texture => &mut self.textures,
+            VertexArray => &mut self.vertex_arrays,
+            Framebuffer => &mut self.framebuffers,
+            Sampler => &mut self.samplers,
+        }
+    }
+
+    /// Number of alive objects of all kinds
+    pub fn live(&self) -> usize {
+        self.shaders.live() + self.programs.live() + self.buffers.live() + self.textures.live() +
+            self.vertex_arrays.live() + self.framebuffers.live() + self.samplers.live()
+    }
+
+    /// Number of leaked objects of all kinds
+    pub fn leaked(&self) -> usize {
+        self.shaders.leaked + self.programs.leaked + self.buffers.leaked + self.textures.leaked +
+            self.vertex_arrays.leaked + self.framebuffers.leaked + self.samplers.leaked
+    }
+}
+
+/// Record of tracked GL object
+#[cfg(feature = "debug-resources")]
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceRecord {
+    /// Kind of object
+    pub object: Object,
+    /// Where object was created
+    pub location: &'static Location<'static>,
+}
+
+#[cfg(feature = "debug-resources")]
+impl fmt::Display for ResourceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} created at {}", self.object, self.location)
+    }
+}
+
+/// Get records of alive objects in order of creation
+#[cfg(feature = "debug-resources")]
+pub fn live_resources() -> Vec<ResourceRecord> {
+    tracker().live.values().copied().collect()
+}
+
+/// Take records of objects which was dropped without deletion since last call
+#[cfg(feature = "debug-resources")]
+pub fn take_leaks() -> Vec<ResourceRecord> {
+    core::mem::take(&mut tracker().leaks)
+}
+
+#[cfg(feature = "debug-resources")]
+struct Tracker {
+    next_id: u64,
+    stats: ResourceStats,
+    live: BTreeMap<u64, ResourceRecord>,
+    leaks: Vec<ResourceRecord>,
+}
+
+#[cfg(feature = "debug-resources")]
+static TRACKER: Mutex<Tracker> = Mutex::new(Tracker {
+    next_id: 0,
+    stats: ResourceStats::new(),
+    live: BTreeMap::new(),
+    leaks: Vec::new(),
+});
+
+#[cfg(feature = "debug-resources")]
+fn tracker() -> std::sync::MutexGuard<'static, Tracker> {
+    TRACKER.lock().unwrap_or_else(|error| error.into_inner())
+}
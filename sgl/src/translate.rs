@@ -0,0 +1,691 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+};
+use super::{HasContext, ShaderType, Dialect, preprocess::directive, context::Current};
+
+/// GLSL language version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Es100,
+    Es300,
+    /// Desktop GLSL with version number
+    Glsl(u16),
+}
+
+impl Lang {
+    /// Parse arguments of `#version` directive
+    fn parse(version: &str) -> Option<Self> {
+        let mut words = version.split_whitespace();
+        match (words.next()?, words.next()) {
+            ("100", None) => Some(Lang::Es100),
+            ("300", Some("es")) => Some(Lang::Es300),
+            ("110", None) => Some(Lang::Glsl(110)),
+            ("120", None) => Some(Lang::Glsl(120)),
+            ("130", None) => Some(Lang::Glsl(130)),
+            ("140", None) => Some(Lang::Glsl(140)),
+            ("150", None) | ("150", Some("core")) => Some(Lang::Glsl(150)),
+            ("330", None) | ("330", Some("core")) => Some(Lang::Glsl(330)),
+            _ => None,
+        }
+    }
+
+    fn version(&self) -> &'static str {
+        match self {
+            Lang::Es100 => "100",
+            Lang::Es300 => "300 es",
+            Lang::Glsl(110) => "110",
+            Lang::Glsl(120) => "120",
+            Lang::Glsl(130) => "130",
+            Lang::Glsl(140) => "140",
+            Lang::Glsl(150) => "150",
+            Lang::Glsl(_) => "330 core",
+        }
+    }
+
+    /// Language uses `attribute`, `varying` and built-in fragment outputs
+    fn is_legacy(&self) -> bool {
+        match self {
+            Lang::Es100 => true,
+            Lang::Es300 => false,
+            Lang::Glsl(version) => *version < 130,
+        }
+    }
+
+    fn is_es(&self) -> bool {
+        !matches!(self, Lang::Glsl(_))
+    }
+
+    /// Language has precision qualifiers
+    fn has_precision(&self) -> bool {
+        !matches!(self, Lang::Glsl(version) if *version < 130)
+    }
+
+    /// Language has layout qualifiers
+    fn has_layout(&self) -> bool {
+        !matches!(self, Lang::Es100 | Lang::Glsl(0..=329))
+    }
+}
+
+impl Dialect {
+    /// Detect dialect of current context
+    pub fn detect<G: HasContext>(gl: &G) -> Self {
+        Current::get(gl).caps.dialect()
+    }
+
+    fn lang(&self) -> Lang {
+        use self::Dialect::*;
+        match self {
+            Gles2 | WebGl => Lang::Es100,
+            Gles3 | WebGl2 => Lang::Es300,
+            GlCore => Lang::Glsl(330),
+            Gl(version) => Lang::Glsl(*version),
+        }
+    }
+
+    /// Translate shader source to this dialect
+    ///
+    /// Source language is determined by `#version` directive (`100` when it is missing).
+    /// Translation between GLSL ES 1.00, GLSL ES 3.00 and GLSL 1.10 - 3.30 core is supported,
+    /// sources in other versions is returned as is.
+    ///
+    /// Translation keeps line numbers, so diagnostics still points to original source lines.
+    pub fn translate<'a>(&self, typ: ShaderType, source: &'a str) -> Cow<'a, str> {
+        let tokens = tokenize(source);
+
+        let version = tokens.iter()
+            .filter(|token| token.kind == Kind::Directive)
+            .filter_map(|token| directive(token.text))
+            .find(|(name, _)| *name == "version");
+
+        let from = match version {
+            Some((_, args)) => match Lang::parse(args) {
+                Some(lang) => lang,
+                None => return Cow::Borrowed(source),
+            },
+            None => Lang::Es100,
+        };
+        let to = self.lang();
+
+        if from == to {
+            return Cow::Borrowed(source);
+        }
+
+        let mut output = String::with_capacity(source.len() + 64);
+        if version.is_none() {
+            output.push_str("#version ");
+            output.push_str(to.version());
+            // older versions treats number as the number of directive line itself
+            output.push_str(if to.is_legacy() { "\n#line 0 0\n" } else { "\n#line 1 0\n" });
+        }
+        Translator::new(typ, from, to, &tokens).emit(&mut output);
+
+        Cow::Owned(output)
+    }
+}
+
+/// Extensions which is part of GLSL ES 3.00
+const CORE_EXTENSIONS: &[&str] = &[
+    "GL_OES_standard_derivatives",
+    "GL_EXT_shader_texture_lod",
+    "GL_EXT_frag_depth",
+    "GL_EXT_draw_buffers",
+];
+
+/// Built-in names of GLSL ES 1.00 and its modern replacements
+const RENAMES: &[(&str, &str)] = &[
+    ("gl_FragColor", "sgl_FragColor"),
+    ("gl_FragData", "sgl_FragData"),
+    ("gl_FragDepthEXT", "gl_FragDepth"),
+    ("texture2D", "texture"),
+    ("textureCube", "texture"),
+    ("texture2DProj", "textureProj"),
+    ("texture2DLod", "textureLod"),
+    ("textureCubeLod", "textureLod"),
+    ("texture2DProjLod", "textureProjLod"),
+    ("texture2DLodEXT", "textureLod"),
+    ("textureCubeLodEXT", "textureLod"),
+    ("texture2DProjLodEXT", "textureProjLod"),
+    ("texture2DGradEXT", "textureGrad"),
+    ("textureCubeGradEXT", "textureGrad"),
+];
+
+/// Built-in names of GLSL ES 1.00 extensions and its replacements in GLSL 1.10 and 1.20
+const DESKTOP_RENAMES: &[(&str, &str)] = &[
+    ("gl_FragDepthEXT", "gl_FragDepth"),
+    ("texture2DLodEXT", "texture2DLod"),
+    ("textureCubeLodEXT", "textureCubeLod"),
+    ("texture2DProjLodEXT", "texture2DProjLod"),
+    ("texture2DGradEXT", "texture2DGradARB"),
+    ("textureCubeGradEXT", "textureCubeGradARB"),
+];
+
+/// Qualifiers which doesn't exist in GLSL ES 1.00 and GLSL before 1.30
+const MODERN_QUALIFIERS: &[&str] = &["flat", "smooth", "noperspective", "centroid"];
+
+/// Precision qualifiers which doesn't exist in GLSL before 1.30
+const PRECISIONS: &[&str] = &["lowp", "mediump", "highp"];
+
+struct Translator<'a, 't> {
+    typ: ShaderType,
+    from: Lang,
+    to: Lang,
+    tokens: &'t [Token<'a>],
+    /// Brace and paren nesting before each token
+    scopes: Vec<(u32, u32)>,
+    /// Tokens which should be removed
+    removed: Vec<bool>,
+    /// Names of cube map samplers
+    cubes: HashSet<&'a str>,
+    /// Fragment outputs with locations
+    outputs: Vec<(&'a str, Option<u32>)>,
+    /// Index of first global declaration token
+    first: Option<usize>,
+    /// Source has default precision for floats
+    has_precision: bool,
+}
+
+impl<'a, 't> Translator<'a, 't> {
+    fn new(typ: ShaderType, from: Lang, to: Lang, tokens: &'t [Token<'a>]) -> Self {
+        let mut this = Self {
+            typ, from, to, tokens,
+            scopes: Vec::with_capacity(tokens.len()),
+            removed: vec![false; tokens.len()],
+            cubes: HashSet::new(),
+            outputs: Vec::new(),
+            first: None,
+            has_precision: false,
+        };
+        this.analyze();
+        this
+    }
+
+    fn analyze(&mut self) {
+        let tokens = self.tokens;
+        let (mut depth, mut paren) = (0u32, 0u32);
+        let mut statement = None;
+
+        for (index, token) in tokens.iter().enumerate() {
+            self.scopes.push((depth, paren));
+
+            if statement.is_none() && token.is_code() {
+                statement = Some(index);
+            }
+
+            match token.kind {
+                Kind::Punct => match token.text {
+                    "{" => depth += 1,
+                    "}" => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            statement = None;
+                        }
+                    },
+                    "(" => paren += 1,
+                    ")" => paren = paren.saturating_sub(1),
+                    ";" if depth == 0 => statement = None,
+                    _ => (),
+                },
+                Kind::Ident => match token.text {
+                    "samplerCube" | "samplerCubeShadow" => {
+                        if let Some(name) = self.next(index).filter(|&next| tokens[next].kind == Kind::Ident) {
+                            self.cubes.insert(tokens[name].text);
+                        }
+                    },
+                    "precision" => {
+                        let typ = self.next(index).and_then(|next| self.next(next));
+                        if typ.map(|typ| tokens[typ].text) == Some("float") {
+                            self.has_precision = true;
+                        }
+                        if !self.to.has_precision() {
+                            self.remove_statement(index);
+                        }
+                    },
+                    "out" if depth == 0 && paren == 0 && self.typ == ShaderType::Fragment && self.to.is_legacy() => {
+                        self.remove_output(statement.unwrap_or(index), index);
+                    },
+                    "layout" if depth == 0 && paren == 0 && !self.to.has_layout() => {
+                        self.remove_layout(index);
+                    },
+                    name if depth == 0 && paren == 0 && self.to.is_legacy() && MODERN_QUALIFIERS.contains(&name) => {
+                        self.removed[index] = true;
+                    },
+                    name if !self.to.has_precision() && PRECISIONS.contains(&name) => {
+                        self.removed[index] = true;
+                    },
+                    _ => (),
+                },
+                _ => (),
+            }
+
+            if self.first.is_none() && token.is_code() {
+                self.first = Some(index);
+            }
+        }
+    }
+
+    /// Remove declaration of fragment output and remember its name
+    fn remove_output(&mut self, start: usize, index: usize) {
+        let tokens = self.tokens;
+        let end = match (index..tokens.len()).find(|&end| tokens[end].text == ";") {
+            Some(end) => end,
+            None => return,
+        };
+
+        let name = (start..end).rev()
+            .find(|&name| tokens[name].kind == Kind::Ident)
+            .map(|name| tokens[name].text);
+
+        let location = (start..end)
+            .find(|&location| tokens[location].text == "location")
+            .and_then(|location| self.next(location))
+            .and_then(|equal| self.next(equal))
+            .and_then(|value| tokens[value].text.parse().ok());
+
+        if let Some(name) = name {
+            self.outputs.push((name, location));
+            for removed in &mut self.removed[start..=end] {
+                *removed = true;
+            }
+        }
+    }
+
+    /// Remove statement up to semicolon
+    fn remove_statement(&mut self, index: usize) {
+        let tokens = self.tokens;
+        if let Some(end) = (index..tokens.len()).find(|&end| tokens[end].text == ";") {
+            for removed in &mut self.removed[index..=end] {
+                *removed = true;
+            }
+        }
+    }
+
+    /// Remove layout qualifier with arguments
+    fn remove_layout(&mut self, index: usize) {
+        let tokens = self.tokens;
+        if let Some(end) = (index..tokens.len()).find(|&end| tokens[end].text == ")") {
+            for removed in &mut self.removed[index..=end] {
+                *removed = true;
+            }
+        }
+    }
+
+    /// Find next significant token
+    fn next(&self, index: usize) -> Option<usize> {
+        (index + 1..self.tokens.len()).find(|&next| self.tokens[next].is_code())
+    }
+
+    /// Declarations which should be inserted before first global declaration
+    fn prelude(&self) -> String {
+        let mut prelude = String::new();
+        let uses = |name| self.tokens.iter().any(|token| token.text == name);
+
+        if self.typ == ShaderType::Fragment {
+            if self.from.is_legacy() && !self.to.is_legacy() {
+                if uses("gl_FragColor") {
+                    prelude.push_str("out mediump vec4 sgl_FragColor; ");
+                }
+                if uses("gl_FragData") {
+                    prelude.push_str("out mediump vec4 sgl_FragData[gl_MaxDrawBuffers]; ");
+                }
+            }
+            if !self.from.is_es() && self.to.is_es() && !self.has_precision {
+                prelude.push_str(match self.to {
+                    Lang::Es100 => "precision mediump float; ",
+                    _ => "precision highp float; ",
+                });
+            }
+        }
+
+        prelude
+    }
+
+    fn emit(&self, output: &mut String) {
+        for (index, token) in self.tokens.iter().enumerate() {
+            if self.first == Some(index) {
+                output.push_str(&self.prelude());
+            }
+
+            if self.removed[index] {
+                // keep line numbers
+                output.extend(token.text.chars().filter(|&c| c == '\n'));
+                continue;
+            }
+
+            match token.kind {
+                Kind::Directive => self.emit_directive(output, token.text),
+                Kind::Ident => self.emit_ident(output, index, token.text),
+                _ => output.push_str(token.text),
+            }
+        }
+    }
+
+    fn emit_directive(&self, output: &mut String, text: &str) {
+        match directive(text) {
+            Some(("version", _)) => {
+                output.push_str("#version ");
+                output.push_str(self.to.version());
+            },
+            Some(("extension", args)) if self.from == Lang::Es100 &&
+                CORE_EXTENSIONS.iter().any(|name| args.trim_start().starts_with(name)) => {
+                // derivatives, depth output and draw buffers is part of GLSL 1.10
+                if self.to.is_legacy() && args.trim_start().starts_with("GL_EXT_shader_texture_lod") {
+                    output.push_str(&text.replacen("GL_EXT_shader_texture_lod", "GL_ARB_shader_texture_lod", 1));
+                }
+            },
+            _ => output.push_str(text),
+        }
+    }
+
+    fn emit_ident(&self, output: &mut String, index: usize, name: &'a str) {
+        let (depth, paren) = self.scopes[index];
+        let global = depth == 0 && paren == 0;
+        let vertex = self.typ == ShaderType::Vertex;
+
+        let name = if self.from.is_legacy() && self.to.is_legacy() {
+            DESKTOP_RENAMES.iter()
+                .map(|&(es, desktop)| if self.to.is_es() { (desktop, es) } else { (es, desktop) })
+                .find(|(old, _)| *old == name)
+                .map(|(_, new)| new)
+                .unwrap_or(name)
+        } else if self.from.is_legacy() {
+            match name {
+                "attribute" if global => "in",
+                "varying" if global => if vertex { "out" } else { "in" },
+                _ => RENAMES.iter()
+                    .find(|(old, _)| *old == name)
+                    .map(|(_, new)| *new)
+                    .unwrap_or(name),
+            }
+        } else if self.to.is_legacy() {
+            if let Some(position) = self.outputs.iter().position(|(output, _)| *output == name) {
+                if self.outputs.len() == 1 {
+                    output.push_str("gl_FragColor");
+                } else {
+                    let location = self.outputs[position].1.unwrap_or(position as u32);
+                    output.push_str(&format!("gl_FragData[{}]", location));
+                }
+                return;
+            }
+            let cube = self.is_cube_call(index);
+            match name {
+                "in" if global => if vertex { "attribute" } else { "varying" },
+                "out" if global => "varying",
+                "texture" => if cube { "textureCube" } else { "texture2D" },
+                "textureProj" => "texture2DProj",
+                "textureProjLod" => if vertex || !self.to.is_es() { "texture2DProjLod" } else { "texture2DProjLodEXT" },
+                "textureLod" => match (cube, vertex || !self.to.is_es()) {
+                    (false, true) => "texture2DLod",
+                    (true, true) => "textureCubeLod",
+                    (false, false) => "texture2DLodEXT",
+                    (true, false) => "textureCubeLodEXT",
+                },
+                "textureGrad" => match (cube, self.to.is_es()) {
+                    (false, true) => "texture2DGradEXT",
+                    (true, true) => "textureCubeGradEXT",
+                    (false, false) => "texture2DGradARB",
+                    (true, false) => "textureCubeGradARB",
+                },
+                "gl_FragDepth" if self.to.is_es() => "gl_FragDepthEXT",
+                _ => name,
+            }
+        } else {
+            name
+        };
+
+        output.push_str(name);
+    }
+
+    /// Check that texture function is called with cube map sampler
+    fn is_cube_call(&self, index: usize) -> bool {
+        self.next(index)
+            .filter(|&paren| self.tokens[paren].text == "(")
+            .and_then(|paren| self.next(paren))
+            .map(|sampler| self.cubes.contains(self.tokens[sampler].text))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ident,
+    Number,
+    Punct,
+    Space,
+    Newline,
+    Comment,
+    Directive,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+}
+
+impl<'a> Token<'a> {
+    fn is_code(&self) -> bool {
+        matches!(self.kind, Kind::Ident | Kind::Number | Kind::Punct)
+    }
+}
+
+/// Split source to tokens
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line_start = true;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let rest = &bytes[pos..];
+        let (kind, len) = match rest[0] {
+            b'\n' => (Kind::Newline, 1),
+            b' ' | b'\t' | b'\r' => (Kind::Space, span(rest, |c| c == b' ' || c == b'\t' || c == b'\r')),
+            b'#' if line_start => (Kind::Directive, span(rest, |c| c != b'\n')),
+            b'/' if rest.get(1) == Some(&b'/') => (Kind::Comment, span(rest, |c| c != b'\n')),
+            b'/' if rest.get(1) == Some(&b'*') => (Kind::Comment, rest.windows(2)
+                                                   .skip(2)
+                                                   .position(|end| end == b"*/")
+                                                   .map(|end| end + 4)
+                                                   .unwrap_or(rest.len())),
+            c if c == b'_' || c.is_ascii_alphabetic() => (Kind::Ident, span(rest, |c| c == b'_' || c.is_ascii_alphanumeric())),
+            c if c.is_ascii_digit() || (c == b'.' && rest.get(1).map(u8::is_ascii_digit).unwrap_or(false)) =>
+                (Kind::Number, span(rest, |c| c == b'.' || c == b'_' || c.is_ascii_alphanumeric())),
+            _ => (Kind::Punct, source[pos..].chars().next().map(char::len_utf8).unwrap_or(1)),
+        };
+
+        line_start = match kind {
+            Kind::Newline => true,
+            Kind::Space => line_start,
+            _ => false,
+        };
+
+        tokens.push(Token { kind, text: &source[pos..pos + len] });
+        pos += len;
+    }
+
+    tokens
+}
+
+fn span(bytes: &[u8], pred: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().position(|&c| !pred(c)).unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use self::{Dialect::*, ShaderType::*};
+
+    const VERTEX_100: &str = "attribute vec2 a_pos;\nvarying vec2 v_uv;\nvoid f(in float x, out float y) { y = x; }\nvoid main() { v_uv = a_pos; gl_Position = vec4(a_pos, 0.0, 1.0); }\n";
+
+    const FRAGMENT_100: &str = "#version 100\n#extension GL_OES_standard_derivatives : enable\nprecision mediump float;\nuniform sampler2D t;\nuniform samplerCube c;\nvarying vec2 v_uv;\n/* gl_FragColor */\nvoid main() { gl_FragColor = texture2D(t, v_uv) + textureCube(c, vec3(v_uv, 1.0)); }\n";
+
+    const FRAGMENT_300: &str = "#version 300 es\nprecision highp float;\nlayout(location = 0) out vec4 color;\nlayout(location = 1) out vec4 normal;\nflat in int id;\nuniform samplerCube env;\nin vec3 n;\nvoid main() { color = texture(env, n); normal = textureLod(env, n, 1.0); }\n";
+
+    const FRAGMENT_330: &str = "#version 330 core\nout vec4 color;\nin vec2 uv;\nuniform sampler2D t;\nvoid main() { color = texture(t, uv); gl_FragDepth = 0.5; }\n";
+
+    #[test]
+    fn vertex_100() {
+        let table = [
+            (Gles2, VERTEX_100.into()),
+            (WebGl, VERTEX_100.into()),
+            (Gles3, "#version 300 es\n#line 1 0\nin vec2 a_pos;\nout vec2 v_uv;\nvoid f(in float x, out float y) { y = x; }\nvoid main() { v_uv = a_pos; gl_Position = vec4(a_pos, 0.0, 1.0); }\n".into()),
+            (WebGl2, "#version 300 es\n#line 1 0\nin vec2 a_pos;\nout vec2 v_uv;\nvoid f(in float x, out float y) { y = x; }\nvoid main() { v_uv = a_pos; gl_Position = vec4(a_pos, 0.0, 1.0); }\n".into()),
+            (GlCore, "#version 330 core\n#line 1 0\nin vec2 a_pos;\nout vec2 v_uv;\nvoid f(in float x, out float y) { y = x; }\nvoid main() { v_uv = a_pos; gl_Position = vec4(a_pos, 0.0, 1.0); }\n".into()),
+            (Gl(120), format!("#version 120\n#line 0 0\n{}", VERTEX_100)),
+            (Gl(140), "#version 140\n#line 1 0\nin vec2 a_pos;\nout vec2 v_uv;\nvoid f(in float x, out float y) { y = x; }\nvoid main() { v_uv = a_pos; gl_Position = vec4(a_pos, 0.0, 1.0); }\n".into()),
+        ];
+        check(Vertex, VERTEX_100, &table);
+    }
+
+    #[test]
+    fn fragment_100() {
+        let modern = |version| format!("#version {}\n\nout mediump vec4 sgl_FragColor; precision mediump float;\nuniform sampler2D t;\nuniform samplerCube c;\nin vec2 v_uv;\n/* gl_FragColor */\nvoid main() {{ sgl_FragColor = texture(t, v_uv) + texture(c, vec3(v_uv, 1.0)); }}\n", version);
+        let table = [
+            (Gles2, FRAGMENT_100.into()),
+            (WebGl, FRAGMENT_100.into()),
+            (Gles3, modern("300 es")),
+            (WebGl2, modern("300 es")),
+            (GlCore, modern("330 core")),
+            (Gl(120), "#version 120\n\n\nuniform sampler2D t;\nuniform samplerCube c;\nvarying vec2 v_uv;\n/* gl_FragColor */\nvoid main() { gl_FragColor = texture2D(t, v_uv) + textureCube(c, vec3(v_uv, 1.0)); }\n".into()),
+            (Gl(140), modern("140")),
+        ];
+        check(Fragment, FRAGMENT_100, &table);
+    }
+
+    #[test]
+    fn fragment_300() {
+        let legacy = |version, lod| format!("#version {}\n{}\n\n\n varying int id;\nuniform samplerCube env;\nvarying vec3 n;\nvoid main() {{ gl_FragData[0] = textureCube(env, n); gl_FragData[1] = {}(env, n, 1.0); }}\n", version,
+                                            if version == "100" { "precision highp float;" } else { "" }, lod);
+        let table = [
+            (Gles2, legacy("100", "textureCubeLodEXT")),
+            (WebGl, legacy("100", "textureCubeLodEXT")),
+            (Gles3, FRAGMENT_300.into()),
+            (WebGl2, FRAGMENT_300.into()),
+            (GlCore, FRAGMENT_300.replace("300 es", "330 core")),
+            (Gl(120), legacy("120", "textureCubeLod")),
+            (Gl(140), "#version 140\nprecision highp float;\n out vec4 color;\n out vec4 normal;\nflat in int id;\nuniform samplerCube env;\nin vec3 n;\nvoid main() { color = texture(env, n); normal = textureLod(env, n, 1.0); }\n".into()),
+        ];
+        check(Fragment, FRAGMENT_300, &table);
+    }
+
+    #[test]
+    fn fragment_330() {
+        let legacy = |version, precision, depth| format!("#version {}\n{}\nvarying vec2 uv;\nuniform sampler2D t;\nvoid main() {{ gl_FragColor = texture2D(t, uv); {} = 0.5; }}\n", version, precision, depth);
+        let table = [
+            (Gles2, legacy("100", "precision mediump float; ", "gl_FragDepthEXT")),
+            (WebGl, legacy("100", "precision mediump float; ", "gl_FragDepthEXT")),
+            (Gles3, FRAGMENT_330.replace("330 core\n", "300 es\nprecision highp float; ")),
+            (WebGl2, FRAGMENT_330.replace("330 core\n", "300 es\nprecision highp float; ")),
+            (GlCore, FRAGMENT_330.into()),
+            (Gl(120), legacy("120", "", "gl_FragDepth")),
+            (Gl(140), FRAGMENT_330.replace("330 core", "140")),
+        ];
+        check(Fragment, FRAGMENT_330, &table);
+    }
+
+    fn check(typ: ShaderType, source: &str, table: &[(Dialect, String)]) {
+        for (dialect, expected) in table {
+            let output = dialect.translate(typ, source);
+            assert_eq!(output, expected.as_str(), "{:?}", dialect);
+
+            // line numbers is kept
+            let extra = if source.starts_with("#version") { 0 } else { 2 };
+            if let Cow::Owned(output) = &output {
+                assert_eq!(output.lines().count(), source.lines().count() + extra, "{:?}", dialect);
+            }
+        }
+    }
+
+    #[test]
+    fn unchanged() {
+        assert!(matches!(Gles2.translate(Vertex, VERTEX_100), Cow::Borrowed(_)));
+        assert!(matches!(Gles3.translate(Fragment, FRAGMENT_300), Cow::Borrowed(_)));
+        assert!(matches!(GlCore.translate(Fragment, FRAGMENT_330), Cow::Borrowed(_)));
+
+        // unknown version
+        let source = "#version 450\nvoid main() {}\n";
+        assert!(matches!(Gles2.translate(Vertex, source), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn renames() {
+        for (old, new) in RENAMES {
+            let source = format!("#version 100\nvoid main() {{ {}; }}\n", old);
+            let output = Gles3.translate(Fragment, &source);
+            assert!(output.ends_with(&format!("void main() {{ {}; }}\n", new)), "{}", output);
+        }
+    }
+
+    #[test]
+    fn legacy_renames() {
+        let table = [
+            ("texture(s, p)", "texture2D(s, p)", "texture2D(s, p)", "texture2D(s, p)"),
+            ("texture(c, p)", "textureCube(c, p)", "textureCube(c, p)", "textureCube(c, p)"),
+            ("textureProj(s, p)", "texture2DProj(s, p)", "texture2DProj(s, p)", "texture2DProj(s, p)"),
+            ("textureLod(s, p, l)", "texture2DLod(s, p, l)", "texture2DLodEXT(s, p, l)", "texture2DLod(s, p, l)"),
+            ("textureLod(c, p, l)", "textureCubeLod(c, p, l)", "textureCubeLodEXT(c, p, l)", "textureCubeLod(c, p, l)"),
+            ("textureProjLod(s, p, l)", "texture2DProjLod(s, p, l)", "texture2DProjLodEXT(s, p, l)", "texture2DProjLod(s, p, l)"),
+            ("textureGrad(s, p, x, y)", "texture2DGradEXT(s, p, x, y)", "texture2DGradEXT(s, p, x, y)", "texture2DGradARB(s, p, x, y)"),
+            ("textureGrad(c, p, x, y)", "textureCubeGradEXT(c, p, x, y)", "textureCubeGradEXT(c, p, x, y)", "textureCubeGradARB(c, p, x, y)"),
+        ];
+        let source = |call| format!("#version 300 es\nuniform sampler2D s;\nuniform samplerCube c;\nvoid main() {{ {}; }}\n", call);
+        let output = |version, call| format!("#version {}\nuniform sampler2D s;\nuniform samplerCube c;\nvoid main() {{ {}; }}\n", version, call);
+
+        for (modern, vertex, fragment, desktop) in &table {
+            assert_eq!(Gles2.translate(Vertex, &source(modern)), output("100", vertex));
+            assert_eq!(Gles2.translate(Fragment, &source(modern)), output("100", fragment));
+            assert_eq!(Gl(120).translate(Fragment, &source(modern)), output("120", desktop));
+        }
+    }
+
+    #[test]
+    fn fragment_outputs() {
+        // single output is replaced by gl_FragColor wherever it is used
+        let source = "#version 300 es\nprecision mediump float;\nout vec4 result;\nvec4 f() { return vec4(1.0); }\nvoid main() { result = f(); result.a = 1.0; }\n";
+        assert_eq!(Gles2.translate(Fragment, source),
+                   "#version 100\nprecision mediump float;\n\nvec4 f() { return vec4(1.0); }\nvoid main() { gl_FragColor = f(); gl_FragColor.a = 1.0; }\n");
+
+        // outputs without locations is indexed in order of declaration
+        let source = "#version 300 es\nprecision mediump float;\nout vec4 a; out vec4 b;\nvoid main() { b = a = vec4(0.0); }\n";
+        assert_eq!(Gles2.translate(Fragment, source),
+                   "#version 100\nprecision mediump float;\n \nvoid main() { gl_FragData[1] = gl_FragData[0] = vec4(0.0); }\n");
+
+        // vertex outputs is varyings
+        let source = "#version 300 es\nin vec2 a_pos;\nout vec2 v_uv;\nvoid main() { v_uv = a_pos; }\n";
+        assert_eq!(Gles2.translate(Vertex, source),
+                   "#version 100\nattribute vec2 a_pos;\nvarying vec2 v_uv;\nvoid main() { v_uv = a_pos; }\n");
+    }
+
+    #[test]
+    fn tokens() {
+        let source = "#define X 1\n  # if X\nfloat a = .5e1+x_1; // a\n/* b\n */ a#b\n";
+        let tokens = tokenize(source);
+
+        assert_eq!(tokens.iter().map(|token| token.text).collect::<String>(), source);
+
+        let kinds = tokens.iter()
+            .filter(|token| !matches!(token.kind, Kind::Space | Kind::Newline))
+            .map(|token| (token.kind, token.text))
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, &[
+            (Kind::Directive, "#define X 1"),
+            (Kind::Directive, "# if X"),
+            (Kind::Ident, "float"),
+            (Kind::Ident, "a"),
+            (Kind::Punct, "="),
+            (Kind::Number, ".5e1"),
+            (Kind::Punct, "+"),
+            (Kind::Ident, "x_1"),
+            (Kind::Punct, ";"),
+            (Kind::Comment, "// a"),
+            (Kind::Comment, "/* b\n */"),
+            (Kind::Ident, "a"),
+            (Kind::Punct, "#"),
+            (Kind::Ident, "b"),
+        ]);
+
+        // unterminated comment takes the rest of source
+        assert_eq!(tokenize("/* a").last().map(|token| token.kind), Some(Kind::Comment));
+    }
+}
@@ -1,5 +1,5 @@
 use core::marker::PhantomData;
-use super::{HasContext};
+use super::{Result, HasContext, GlslType, Program};
 
 #[cfg(feature = "glam")]
 mod glam_impls;
@@ -12,13 +12,19 @@ mod colours_impls;
 
 /// Uniform location binding
 pub struct Uniform<G: HasContext, T> {
+    pub(super) name: String,
     pub(super) location: Option<G::UniformLocation>,
     _type: PhantomData<T>,
 }
 
 impl<G: HasContext, T: AsUniform<G>> Uniform<G, T> {
-    pub(super) fn new(location: Option<G::UniformLocation>) -> Self {
-        Self { location: location, _type: PhantomData }
+    pub(super) fn new(name: &str, location: Option<G::UniformLocation>) -> Self {
+        Self { name: name.into(), location: location, _type: PhantomData }
+    }
+
+    /// Uniform name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Load data to uniform location
@@ -29,16 +35,33 @@ impl<G: HasContext, T: AsUniform<G>> Uniform<G, T> {
     }
 }
 
+/// The trait for structs of uniform values which can be loaded at once
+///
+/// Usually it implemented using `#[derive(Uniforms)]`.
+pub trait Uniforms<G: HasContext>: Sized {
+    /// Uniform locations binding
+    type Bindings;
+
+    /// Resolve uniform locations
+    fn bindings(gl: &G, program: &Program<G>) -> Result<Self::Bindings>;
+
+    /// Load all values to uniforms
+    fn load(bindings: &Self::Bindings, gl: &G, values: &Self);
+}
+
 /// The trait for types which can be used as a uniform data
 pub trait AsUniform<G: HasContext> {
+    /// GLSL type of uniform
+    const TYPE: GlslType;
     type Type;
     fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type);
 }
 
 macro_rules! as_uniform_impls {
-    ($($type: ty, $func: ident;)*) => {
+    ($($type: ty, $glsl_type: ident, $func: ident;)*) => {
         $(
             impl<G: HasContext> AsUniform<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), data); }
@@ -49,20 +72,22 @@ macro_rules! as_uniform_impls {
 }
 
 as_uniform_impls! {
-    f32, uniform_1_f32;
-    i32, uniform_1_i32;
+    f32, Float, uniform_1_f32;
+    i32, Int, uniform_1_i32;
 }
 
 macro_rules! as_uniform_impls_tuple {
-    ($($type: ty, $func: ident, ($($arg: tt),+);)*) => {
+    ($($type: ty, $glsl_type: ident, $func: ident, ($($arg: tt),+);)*) => {
         $(
             impl<G: HasContext> AsUniform<G> for &$type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), $(data.$arg),+); }
                 }
             }
             impl<G: HasContext> AsUniform<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), $(data.$arg),+); }
@@ -73,24 +98,26 @@ macro_rules! as_uniform_impls_tuple {
 }
 
 as_uniform_impls_tuple! {
-    (f32, f32), uniform_2_f32, (0, 1);
-    (f32, f32, f32), uniform_3_f32, (0, 1, 2);
-    (f32, f32, f32, f32), uniform_4_f32, (0, 1, 2, 3);
-    (i32, i32), uniform_2_i32, (0, 1);
-    (i32, i32, i32), uniform_3_i32, (0, 1, 2);
-    (i32, i32, i32, i32), uniform_4_i32, (0, 1, 2, 3);
+    (f32, f32), Vec2, uniform_2_f32, (0, 1);
+    (f32, f32, f32), Vec3, uniform_3_f32, (0, 1, 2);
+    (f32, f32, f32, f32), Vec4, uniform_4_f32, (0, 1, 2, 3);
+    (i32, i32), IVec2, uniform_2_i32, (0, 1);
+    (i32, i32, i32), IVec3, uniform_3_i32, (0, 1, 2);
+    (i32, i32, i32, i32), IVec4, uniform_4_i32, (0, 1, 2, 3);
 }
 
 macro_rules! as_uniform_impls_array_ref {
-    ($($type: ty, $func: ident, $size: tt;)*) => {
+    ($($type: ty, $glsl_type: ident, $func: ident, $size: tt;)*) => {
         $(
             impl<G: HasContext> AsUniform<G> for &[$type; $size] {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), data); }
                 }
             }
             impl<G: HasContext> AsUniform<G> for [$type; $size] {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), &data); }
@@ -101,23 +128,24 @@ macro_rules! as_uniform_impls_array_ref {
 }
 
 as_uniform_impls_array_ref! {
-    f32, uniform_1_f32_slice, 1;
-    f32, uniform_2_f32_slice, 2;
-    f32, uniform_3_f32_slice, 3;
-    f32, uniform_4_f32_slice, 4;
-    i32, uniform_1_i32_slice, 1;
-    i32, uniform_2_i32_slice, 2;
-    i32, uniform_3_i32_slice, 3;
-    i32, uniform_4_i32_slice, 4;
+    f32, Float, uniform_1_f32_slice, 1;
+    f32, Vec2, uniform_2_f32_slice, 2;
+    f32, Vec3, uniform_3_f32_slice, 3;
+    f32, Vec4, uniform_4_f32_slice, 4;
+    i32, Int, uniform_1_i32_slice, 1;
+    i32, IVec2, uniform_2_i32_slice, 2;
+    i32, IVec3, uniform_3_i32_slice, 3;
+    i32, IVec4, uniform_4_i32_slice, 4;
 }
 
 pub struct Direct<T>(T);
 pub struct Transposed<T>(T);
 
 macro_rules! as_uniform_impls_mat_array_ref {
-    ($($conv:tt, $tran:tt, $type: ty, $func: ident, $size: tt;)*) => {
+    ($($conv:tt, $tran:tt, $type: ty, $glsl_type: ident, $func: ident, $size: tt;)*) => {
         $(
             impl<'a, G: HasContext> AsUniform<G> for $conv<&'a [$type; $size]> {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = &'a [$type; $size];
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), $tran, data); }
@@ -128,10 +156,10 @@ macro_rules! as_uniform_impls_mat_array_ref {
 }
 
 as_uniform_impls_mat_array_ref! {
-    Direct, false, f32, uniform_matrix_2_f32_slice, 4;
-    Direct, false, f32, uniform_matrix_3_f32_slice, 9;
-    Direct, false, f32, uniform_matrix_4_f32_slice, 16;
-    Transposed, true, f32, uniform_matrix_2_f32_slice, 4;
-    Transposed, true, f32, uniform_matrix_3_f32_slice, 9;
-    Transposed, true, f32, uniform_matrix_4_f32_slice, 16;
+    Direct, false, f32, Mat2, uniform_matrix_2_f32_slice, 4;
+    Direct, false, f32, Mat3, uniform_matrix_3_f32_slice, 9;
+    Direct, false, f32, Mat4, uniform_matrix_4_f32_slice, 16;
+    Transposed, true, f32, Mat2, uniform_matrix_2_f32_slice, 4;
+    Transposed, true, f32, Mat3, uniform_matrix_3_f32_slice, 9;
+    Transposed, true, f32, Mat4, uniform_matrix_4_f32_slice, 16;
 }
@@ -1,16 +1,18 @@
 use stretch::geometry::{Point, Size, Rect};
-use super::{HasContext, AsUniform};
+use super::{HasContext, GlslType, AsUniform};
 
 macro_rules! as_uniform_impls {
-    ($($type: ty, ($($field:ident),+), $func: ident;)*) => {
+    ($($type: ty, ($($field:ident),+), $glsl_type: ident, $func: ident;)*) => {
         $(
             impl<G: HasContext> AsUniform<G> for $type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), $(data.$field),+); }
                 }
             }
             impl<G: HasContext> AsUniform<G> for &$type {
+                const TYPE: GlslType = GlslType::$glsl_type;
                 type Type = Self;
                 fn uniform_load(gl: &G, location: &G::UniformLocation, data: Self::Type) {
                     unsafe { gl.$func(Some(location.clone()), $(data.$field),+); }
@@ -21,7 +23,7 @@ macro_rules! as_uniform_impls {
 }
 
 as_uniform_impls! {
-    Point<f32>, (x, y), uniform_2_f32;
-    Size<f32>, (width, height), uniform_2_f32;
-    Rect<f32>, (start, end, top, bottom), uniform_4_f32;
+    Point<f32>, (x, y), Vec2, uniform_2_f32;
+    Size<f32>, (width, height), Vec2, uniform_2_f32;
+    Rect<f32>, (start, end, top, bottom), Vec4, uniform_4_f32;
 }
@@ -0,0 +1,58 @@
+use core::mem::size_of;
+use super::{Result, Error, GL, HasContext};
+
+/// Check that some program is bound
+pub(super) fn check_program<G: HasContext>(gl: &G) -> Result<()> {
+    if unsafe { gl.get_parameter_i32(GL::CURRENT_PROGRAM) } == 0 {
+        return Err(Error::Draw("no program is bound".into()));
+    }
+    Ok(())
+}
+
+/// Get number of vertices in buffer checking that buffer element matches attributes stride
+pub(super) fn vertices<T>(length: i32, stride: i32) -> Result<i32> {
+    let size = size_of::<T>() as i32;
+    if size != stride {
+        return Err(Error::Draw(format!("buffer element size {} doesn't match attributes stride {}", size, stride)));
+    }
+    Ok(length)
+}
+
+/// Check that range of vertices fits to buffer
+pub(super) fn check_vertices(first: i32, count: i32, vertices: i32) -> Result<()> {
+    if first < 0 {
+        return Err(Error::Draw(format!("first vertex {} is negative", first)));
+    }
+    if first + count > vertices {
+        return Err(Error::Draw(format!("vertices {}..{} exceed the number of vertices {}", first, first + count, vertices)));
+    }
+    Ok(())
+}
+
+/// Check that range of indexes fits to buffer and indexes doesn't exceed the number of vertices
+///
+/// Restart index is skipped when primitive restart is enabled.
+pub(super) fn check_indices(indices: &[u32], first: i32, count: i32, restart: Option<u32>, base_vertex: i32, vertices: i32) -> Result<()> {
+    if first < 0 || first as usize + count as usize > indices.len() {
+        return Err(Error::Draw(format!("indexes {}..{} exceed the number of indexes {}", first, first + count, indices.len())));
+    }
+    for (position, index) in indices[first as usize..][..count as usize].iter().enumerate() {
+        if Some(*index) == restart {
+            continue;
+        }
+        let vertex = *index as i64 + base_vertex as i64;
+        if vertex < 0 || vertex >= vertices as i64 {
+            return Err(Error::Draw(format!("index {} at {} refers to vertex {} but the number of vertices is {}",
+                                           index, first as usize + position, vertex, vertices)));
+        }
+    }
+    Ok(())
+}
+
+/// Check that per-instance stream has enough elements
+pub(super) fn check_instances(instances: i32, length: i32) -> Result<()> {
+    if instances > length {
+        return Err(Error::Draw(format!("instances {} exceed the per-instance stream length {}", instances, length)));
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use super::Pod;
+
+macro_rules! vector_types {
+    ($($(#[$meta: meta])* $name: ident, $($field: ident: $T: ident),+;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            #[repr(C)]
+            pub struct $name<T> {
+                $(pub $field: T,)+
+            }
+
+            impl<T> $name<T> {
+                pub const fn new($($field: T),+) -> Self {
+                    Self { $($field),+ }
+                }
+            }
+
+            unsafe impl<T: Pod> Pod for $name<T> {}
+
+            impl<T> From<($($T),+)> for $name<T> {
+                fn from(($($field),+): ($($T),+)) -> Self {
+                    Self { $($field),+ }
+                }
+            }
+
+            impl<T> From<$name<T>> for ($($T),+) {
+                fn from(vector: $name<T>) -> Self {
+                    ($(vector.$field),+)
+                }
+            }
+        )*
+    };
+}
+
+vector_types! {
+    /// Vector of two components with defined layout
+    Vector2, x: T, y: T;
+    /// Vector of three components with defined layout
+    Vector3, x: T, y: T, z: T;
+    /// Vector of four components with defined layout
+    Vector4, x: T, y: T, z: T, w: T;
+}
@@ -0,0 +1,70 @@
+use core::{
+    mem::size_of,
+    marker::PhantomData,
+};
+use super::{HasContext, GlslType, AsAttrib, Attribs, context::Current};
+
+/// Field of vertex struct
+pub struct VertexField<G> {
+    /// Attribute name
+    pub name: &'static str,
+    /// Offset of field in bytes
+    pub offset: usize,
+    /// GLSL type of field
+    pub typ: GlslType,
+    /// Setup attribute pointer for field
+    pub pointer: fn(&G, u32, i32, i32),
+}
+
+impl<G: HasContext> VertexField<G> {
+    /// Create vertex field of specified type
+    pub fn new<T: AsAttrib<G>>(name: &'static str, offset: usize) -> Self {
+        Self { name, offset, typ: T::TYPE, pointer: T::attrib_pointer }
+    }
+}
+
+/// The trait for structs which can be used as interleaved vertex data
+///
+/// Usually it implemented using `#[derive(Vertex, Pod)]` on `#[repr(C)]` struct.
+pub trait Vertex<G: HasContext>: Sized {
+    /// Get fields of vertex
+    fn fields() -> Vec<VertexField<G>>;
+}
+
+/// Attributes binding for vertex struct
+pub struct VertexAttribs<G: HasContext, V> {
+    pub(super) attribs: Vec<(u32, VertexField<G>)>,
+    _vertex: PhantomData<V>,
+}
+
+impl<G: HasContext, V: Vertex<G>> VertexAttribs<G, V> {
+    pub(super) fn new(attribs: Vec<(u32, VertexField<G>)>) -> Self {
+        Self { attribs, _vertex: PhantomData }
+    }
+}
+
+impl<G: HasContext, V: Vertex<G>> Attribs<G> for VertexAttribs<G, V> {
+    type Type = V;
+
+    fn enable_attribs(&self, gl: &G) {
+        let stride = size_of::<V>() as i32;
+
+        for (attrib, field) in &self.attribs {
+            unsafe { gl.enable_vertex_attrib_array(*attrib); }
+            (field.pointer)(gl, *attrib, field.offset as i32, stride);
+        }
+    }
+
+    fn disable_attribs(&self, gl: &G) {
+        for (attrib, _) in &self.attribs {
+            unsafe { gl.disable_vertex_attrib_array(*attrib); }
+        }
+    }
+
+    fn set_divisor(&self, gl: &G, divisor: u32) {
+        let current = Current::get(gl);
+        for (attrib, _) in &self.attribs {
+            unsafe { current.vertex_attrib_divisor(gl, *attrib, divisor); }
+        }
+    }
+}
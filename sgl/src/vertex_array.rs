@@ -0,0 +1,141 @@
+use super::{Result, Object, GL, HasContext, Geometry, DrawMode, Resource, DeletionQueue, context::{Current, VertexArrayObject}, track::Track};
+
+/// Geometry with captured buffer and attribute bindings
+///
+/// Uses vertex array object on OpenGL ES 3.0+, OpenGL 3.0+, WebGL 2.0
+/// and with `OES_vertex_array_object` extension, so drawing needs only single bind call.
+/// Otherwise falls back to binding buffers and attributes on each draw.
+///
+/// NOTE: When buffer objects of geometry is replaced (like [`Buffer::grow`](super::Buffer::grow) does)
+/// the bindings should be captured again using [`VertexArray::update`].
+pub struct VertexArray<G: HasContext, D: Geometry<G>> {
+    handle: Handle<G>,
+    geometry: D,
+}
+
+/// Vertex array object handle
+struct Handle<G: HasContext> {
+    vertex_array: Option<VertexArrayObject<G>>,
+    queue: Option<DeletionQueue<G>>,
+    track: Track,
+}
+
+impl<G: HasContext, D: Geometry<G>> VertexArray<G, D> {
+    /// Capture bindings of geometry
+    #[cfg_attr(feature = "debug-resources", track_caller)]
+    pub fn new(gl: &G, geometry: D) -> Result<Self> where G: 'static {
+        let current = Current::get(gl);
+        let vertex_array = if current.has_vertex_array() {
+            Some(unsafe { current.create_vertex_array(gl)? })
+        } else {
+            None
+        };
+
+        let handle = Handle { vertex_array, queue: DeletionQueue::current(), track: Track::new(Object::VertexArray) };
+        let this = Self { handle, geometry };
+        this.update(gl);
+        Ok(this)
+    }
+
+    /// Attach vertex array to deletion queue
+    ///
+    /// Attached vertex array is scheduled for deletion when dropped.
+    /// New vertex array is attached to current queue (see [`DeletionQueue::make_current`]), this replaces it.
+    pub fn attach(mut self, queue: &DeletionQueue<G>) -> Self {
+        self.handle.queue = Some(queue.clone());
+        self
+    }
+
+    /// Delete vertex array object and get back geometry
+    pub fn del(self, gl: &G) -> D {
+        let Self { mut handle, geometry } = self;
+        handle.queue = None;
+        handle.track.release();
+        match handle.vertex_array.take() {
+            Some(VertexArrayObject::Core(vertex_array)) => unsafe { gl.delete_vertex_array(vertex_array) },
+            Some(VertexArrayObject::Oes(name)) => unsafe { Current::get(gl).delete_vertex_array_oes(name) },
+            None => (),
+        }
+        geometry
+    }
+
+    /// Real vertex array object is used
+    pub fn is_native(&self) -> bool {
+        self.handle.vertex_array.is_some()
+    }
+
+    /// Underlying geometry
+    pub fn geometry(&self) -> &D {
+        &self.geometry
+    }
+
+    /// Capture bindings of geometry again
+    pub fn update(&self, gl: &G) {
+        if let Some(vertex_array) = &self.handle.vertex_array {
+            let current = Current::get(gl);
+            unsafe { current.bind_vertex_array(gl, Some(vertex_array)); }
+            self.geometry.bind(gl);
+            // unbind vertex array first to keep captured element array binding
+            unsafe {
+                current.bind_vertex_array::<G>(gl, None);
+                gl.bind_buffer(GL::ARRAY_BUFFER, None);
+                gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, None);
+            }
+        }
+    }
+}
+
+impl<G: HasContext, D: Geometry<G>> Geometry<G> for VertexArray<G, D> {
+    fn bind(&self, gl: &G) {
+        if let Some(vertex_array) = &self.handle.vertex_array {
+            unsafe { Current::get(gl).bind_vertex_array(gl, Some(vertex_array)); }
+        } else {
+            self.geometry.bind(gl);
+        }
+    }
+    fn unbind(&self, gl: &G) {
+        if self.handle.vertex_array.is_some() {
+            unsafe { Current::get(gl).bind_vertex_array::<G>(gl, None); }
+        } else {
+            self.geometry.unbind(gl);
+        }
+    }
+    fn draw_bound(&self, gl: &G, mode: DrawMode, offset: i32, count: i32) {
+        self.geometry.draw_bound(gl, mode, offset, count);
+    }
+    fn draw_bound_instanced(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32) {
+        self.geometry.draw_bound_instanced(gl, mode, offset, count, instances);
+    }
+    fn draw_bound_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, base_vertex: i32) {
+        self.geometry.draw_bound_base_vertex(gl, mode, offset, count, base_vertex);
+    }
+    fn draw_bound_instanced_base_vertex(&self, gl: &G, mode: DrawMode, offset: i32, count: i32, instances: i32, base_vertex: i32) {
+        self.geometry.draw_bound_instanced_base_vertex(gl, mode, offset, count, instances, base_vertex);
+    }
+    fn length(&self) -> i32 {
+        self.geometry.length()
+    }
+    fn is_instanced(&self) -> bool {
+        self.geometry.is_instanced()
+    }
+    fn is_indexed(&self) -> bool {
+        self.geometry.is_indexed()
+    }
+    #[cfg(feature = "validate")]
+    fn validate(&self, offset: i32, count: i32, instances: Option<i32>, base_vertex: i32, restart: bool) -> Result<()> {
+        self.geometry.validate(offset, count, instances, base_vertex, restart)
+    }
+}
+
+impl<G: HasContext> Drop for Handle<G> {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            match self.vertex_array.take() {
+                Some(VertexArrayObject::Core(vertex_array)) => queue.push(Resource::VertexArray(vertex_array)),
+                Some(VertexArrayObject::Oes(name)) => queue.push(Resource::VertexArrayOes(name)),
+                None => (),
+            }
+            self.track.release();
+        }
+    }
+}